@@ -45,6 +45,10 @@ impl Output for Account {
     fn to_json(&self) -> String {
         serde_json::to_string_pretty(self).unwrap()
     }
+
+    fn is_repeatable(&self) -> bool {
+        true
+    }
 }
 
 // impl From<SolanaAccount> for Account {
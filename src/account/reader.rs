@@ -1,12 +1,15 @@
 use crate::{
+    asset::get_das_asset,
     balance::{Balance, SplBalance},
     magiceden::{self, cm},
     metaplex::{
         core::{CoreAssetV1, CoreCollectionV1},
         das as mpl_das,
+        offchain::{OffchainJson, OffchainMetadata, OnchainNames},
     },
     output::{print_error, print_warning, OutputFormat},
     page::Page,
+    pretty::uri::PrettyUri,
     rpc,
     token::{TokenAccount, TokenMetadata, TokenMint},
 };
@@ -15,7 +18,7 @@ use solana_account_decoder_client_types::UiAccountEncoding;
 use solana_client::{
     client_error::ClientError as RpcClientError,
     rpc_config::{self},
-    rpc_request::{self, RpcError},
+    rpc_request::{self},
     rpc_response::{self},
 };
 use solana_sdk::{
@@ -24,10 +27,13 @@ use solana_sdk::{
     program_pack::Pack,
     pubkey::Pubkey,
 };
-use std::{process::exit, str::FromStr};
+use mpl_token_metadata::types::TokenStandard;
+use std::{process::exit, str::FromStr, time::Duration};
 
 use super::Account;
 
+const OFFCHAIN_METADATA_TIMEOUT: Duration = Duration::from_secs(10);
+
 // fn read_program_idl(pubkey: &Pubkey) {
 //     // TODO: handle inconsistency here we print JSON every time despite command format param/flag
 //     let idl_addr = IdlAccount::address(pubkey);
@@ -53,7 +59,13 @@ use super::Account;
 // }
 
 /// Main entry point to account command/module
-pub fn read_account(address: &str, output_format: OutputFormat) {
+pub fn read_account(address: &str, output_format: OutputFormat, offchain: bool) {
+    show_account_page(address, Page::new(output_format), offchain);
+}
+
+/// Adds the account to the page and displays it, other commands use it to show the account
+/// on the same page as their own output
+pub fn show_account_page(address: &str, mut page: Page, offchain: bool) {
     let acc_pubkey = match Pubkey::from_str(address) {
         Ok(pubkey) => pubkey,
         Err(_) => {
@@ -64,8 +76,6 @@ pub fn read_account(address: &str, output_format: OutputFormat) {
         }
     };
 
-    let mut page = Page::new(output_format);
-
     let account = match get_account(&acc_pubkey) {
         Ok(account) => account,
         Err(err) => {
@@ -74,7 +84,15 @@ pub fn read_account(address: &str, output_format: OutputFormat) {
                 // it can be a Metaplex Digital asset (DAS)
                 match get_das_asset(&acc_pubkey) {
                     Ok(asset) => {
+                        let offchain_metadata = if offchain {
+                            read_das_offchain_metadata(&asset)
+                        } else {
+                            None
+                        };
                         page.add(asset);
+                        if let Some(offchain_metadata) = offchain_metadata {
+                            page.add(offchain_metadata);
+                        }
                         page.display();
                         exit(0);
                     }
@@ -109,17 +127,25 @@ pub fn read_account(address: &str, output_format: OutputFormat) {
                 &[1, 0, 0, 0] | &[0, 0, 0, 0] => {
                     // mint account: 1000 NFT, 0000 FT
                     let unpacked_data = spl_token::state::Mint::unpack(&account.data).unwrap();
+                    let decimals = unpacked_data.decimals;
                     page.add(TokenMint::from(unpacked_data));
                     let (metadata_pda, _) =
                         mpl_token_metadata::accounts::Metadata::find_pda(&acc_pubkey);
                     let metadata_account = get_account(&metadata_pda).unwrap();
                     page.add(Account::new(&metadata_pda, &metadata_account));
-                    page.add(TokenMetadata::from(
-                        mpl_token_metadata::accounts::Metadata::safe_deserialize(
-                            metadata_account.data(),
-                        )
-                        .unwrap(),
-                    ));
+                    let metadata = mpl_token_metadata::accounts::Metadata::safe_deserialize(
+                        metadata_account.data(),
+                    )
+                    .unwrap();
+                    let offchain_metadata = if offchain {
+                        read_token_offchain_metadata(&metadata, decimals == 0)
+                    } else {
+                        None
+                    };
+                    page.add(TokenMetadata::from(metadata));
+                    if let Some(offchain_metadata) = offchain_metadata {
+                        page.add(offchain_metadata);
+                    }
                 }
                 _ => {
                     // token account
@@ -138,12 +164,42 @@ pub fn read_account(address: &str, output_format: OutputFormat) {
                 5 => {
                     let unpacked_data =
                         mpl_core::accounts::BaseCollectionV1::from_bytes(&account.data).unwrap();
+                    let offchain_metadata = if offchain {
+                        read_offchain_metadata(
+                            unpacked_data.uri.clone().into(),
+                            &OnchainNames {
+                                name: &unpacked_data.name,
+                                symbol: None,
+                                is_nft: false,
+                            },
+                        )
+                    } else {
+                        None
+                    };
                     page.add(CoreCollectionV1::from(unpacked_data));
+                    if let Some(offchain_metadata) = offchain_metadata {
+                        page.add(offchain_metadata);
+                    }
                 }
                 1 => {
                     let unpacked_data =
                         mpl_core::accounts::BaseAssetV1::from_bytes(&account.data).unwrap();
+                    let offchain_metadata = if offchain {
+                        read_offchain_metadata(
+                            unpacked_data.uri.clone().into(),
+                            &OnchainNames {
+                                name: &unpacked_data.name,
+                                symbol: None,
+                                is_nft: true,
+                            },
+                        )
+                    } else {
+                        None
+                    };
                     page.add(CoreAssetV1::from(unpacked_data));
+                    if let Some(offchain_metadata) = offchain_metadata {
+                        page.add(offchain_metadata);
+                    }
                 }
                 _ => todo!(),
             }
@@ -166,7 +222,15 @@ pub fn read_account(address: &str, output_format: OutputFormat) {
             let unpacked_data =
                 mpl_token_metadata::accounts::Metadata::safe_deserialize(&metadata_acc.data)
                     .unwrap();
+            let offchain_metadata = if offchain {
+                read_token_offchain_metadata(&unpacked_data, true)
+            } else {
+                None
+            };
             page.add(TokenMetadata::from(unpacked_data));
+            if let Some(offchain_metadata) = offchain_metadata {
+                page.add(offchain_metadata);
+            }
         }
         // Magic Eden Candy Machine
         SolanaAccount {
@@ -236,6 +300,55 @@ pub fn read_account(address: &str, output_format: OutputFormat) {
     page.display();
 }
 
+/// Off-chain metadata of Token Metadata program NFTs and fungible tokens,
+/// `maybe_nft` is false when the mint can't be an NFT (e.g. it has decimals)
+fn read_token_offchain_metadata(
+    metadata: &mpl_token_metadata::accounts::Metadata,
+    maybe_nft: bool,
+) -> Option<OffchainMetadata> {
+    let is_nft = maybe_nft
+        && !matches!(
+            metadata.token_standard,
+            Some(TokenStandard::Fungible) | Some(TokenStandard::FungibleAsset)
+        );
+    read_offchain_metadata(
+        metadata.uri.clone().into(),
+        &OnchainNames {
+            name: metadata.name.trim_end_matches('\0'),
+            symbol: Some(metadata.symbol.trim_end_matches('\0')),
+            is_nft,
+        },
+    )
+}
+
+fn read_das_offchain_metadata(asset: &mpl_das::Asset) -> Option<OffchainMetadata> {
+    let is_nft = !matches!(asset.interface.as_str(), "FungibleToken" | "FungibleAsset");
+    read_offchain_metadata(
+        asset.content.json_uri.clone().unwrap_or_default(),
+        &OnchainNames {
+            name: &asset.content.metadata.name,
+            symbol: Some(&asset.content.metadata.symbol),
+            is_nft,
+        },
+    )
+}
+
+/// Fetch and validate off-chain metadata JSON, failures are reported as warnings
+/// and do not stop reading the account
+fn read_offchain_metadata(uri: PrettyUri, onchain: &OnchainNames) -> Option<OffchainMetadata> {
+    if uri.is_empty() {
+        print_warning("off-chain metadata URI is empty");
+        return None;
+    }
+    match get_offchain_json(&uri.resolve()) {
+        Ok(json) => Some(OffchainMetadata::new(uri, json, onchain)),
+        Err(err) => {
+            print_warning(format!("can't read off-chain metadata from {}: {}", uri, err).as_str());
+            None
+        }
+    }
+}
+
 fn get_account(pubkey: &Pubkey) -> Result<SolanaAccount, RpcClientError> {
     let rpc_con = rpc::init_connection();
     rpc_con.get_account(pubkey)
@@ -247,27 +360,14 @@ fn get_multiple_accounts(pubkeys: &[Pubkey]) -> Result<Vec<Option<SolanaAccount>
     rpc_con.get_multiple_accounts(pubkeys)
 }
 
-fn get_das_asset(pubkey: &Pubkey) -> Result<mpl_das::Asset, RpcClientError> {
-    let rpc_con = rpc::init_connection();
-    let res = rpc_con.send::<mpl_das::Asset>(
-        rpc_request::RpcRequest::Custom { method: "getAsset" },
-        json!([pubkey.to_string()]),
-    );
-    match res {
-        Err(err) => {
-            let err_kind = err.kind();
-            match err_kind {
-                solana_client::client_error::ClientErrorKind::RpcError(
-                    RpcError::RpcResponseError { code: -32601, .. },
-                ) => {
-                    println!("RPC does not support DAS API");
-                    exit(1);
-                }
-                _ => Err(err),
-            }
-        }
-        _ => res,
-    }
+fn get_offchain_json(uri: &str) -> Result<OffchainJson, reqwest::Error> {
+    reqwest::blocking::Client::builder()
+        .timeout(OFFCHAIN_METADATA_TIMEOUT)
+        .build()?
+        .get(uri)
+        .send()?
+        .error_for_status()?
+        .json()
 }
 
 fn get_spl_tokens_by_owner(
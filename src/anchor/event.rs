@@ -0,0 +1,368 @@
+use std::fmt;
+
+use serde::{
+    ser::{SerializeMap, SerializeSeq},
+    Serialize, Serializer,
+};
+use serde_json::Value;
+use solana_sdk::pubkey::Pubkey;
+
+use super::idl::{Idl, IdlField};
+use crate::pretty::public_key::PrettyPublicKey;
+
+/// nesting limit of decoded values, guards against recursive type definitions
+const MAX_DEPTH: usize = 32;
+
+/// Anchor event emitted as `Program data:` log, decoded with the IDL of the program
+/// that emitted it
+#[derive(Serialize)]
+pub struct AnchorEvent {
+    pub name: String,
+    pub fields: Vec<EventField>,
+}
+
+#[derive(Serialize)]
+pub struct EventField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: String,
+    pub value: IdlValue,
+}
+
+/// e.g. `SwapEvent { amount_in: 1000 (u64), user: 7xKX..sAsU (pubkey) }`
+impl fmt::Debug for AnchorEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut event = f.debug_struct(&self.name);
+        for field in &self.fields {
+            event.field(&field.name, &Typed(&field.value, &field.ty));
+        }
+        event.finish()
+    }
+}
+
+struct Typed<'a>(&'a IdlValue, &'a str);
+
+impl fmt::Debug for Typed<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.0, f)?;
+        write!(f, " ({})", self.1)
+    }
+}
+
+/// Value of an IDL type decoded from Borsh
+pub enum IdlValue {
+    Bool(bool),
+    Unsigned(u128),
+    Signed(i128),
+    Float(f64),
+    String(String),
+    PublicKey(PrettyPublicKey),
+    Bytes(Vec<u8>),
+    Option(Option<Box<IdlValue>>),
+    List(Vec<IdlValue>),
+    Struct(Vec<(String, IdlValue)>),
+    Enum {
+        variant: String,
+        /// `Struct` or `List` of tuple fields, `None` for unit variants
+        fields: Option<Box<IdlValue>>,
+    },
+}
+
+/// struct field name printed without quotes
+struct FieldName<'a>(&'a str);
+
+impl fmt::Debug for FieldName<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+impl fmt::Debug for IdlValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IdlValue::Bool(value) => write!(f, "{}", value),
+            IdlValue::Unsigned(value) => write!(f, "{}", value),
+            IdlValue::Signed(value) => write!(f, "{}", value),
+            IdlValue::Float(value) => write!(f, "{}", value),
+            IdlValue::String(value) => write!(f, "{:?}", value),
+            IdlValue::PublicKey(value) => write!(f, "{:?}", value),
+            IdlValue::Bytes(value) => write!(f, "{:?}", value),
+            IdlValue::Option(None) => write!(f, "None"),
+            IdlValue::Option(Some(value)) => f.debug_tuple("Some").field(value).finish(),
+            IdlValue::List(values) => f.debug_list().entries(values).finish(),
+            IdlValue::Struct(fields) => f
+                .debug_map()
+                .entries(fields.iter().map(|(name, value)| (FieldName(name), value)))
+                .finish(),
+            IdlValue::Enum {
+                variant,
+                fields: None,
+            } => f.write_str(variant),
+            IdlValue::Enum {
+                variant,
+                fields: Some(fields),
+            } => {
+                write!(f, "{} ", variant)?;
+                fmt::Debug::fmt(fields, f)
+            }
+        }
+    }
+}
+
+/// JSON of decoded values, unit enum variants are strings
+/// and variants with fields are objects of the variant name
+impl Serialize for IdlValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            IdlValue::Bool(value) => serializer.serialize_bool(*value),
+            IdlValue::Unsigned(value) => serializer.serialize_u128(*value),
+            IdlValue::Signed(value) => serializer.serialize_i128(*value),
+            IdlValue::Float(value) => serializer.serialize_f64(*value),
+            IdlValue::String(value) => serializer.serialize_str(value),
+            IdlValue::PublicKey(value) => value.serialize(serializer),
+            IdlValue::Bytes(value) => value.serialize(serializer),
+            IdlValue::Option(value) => value.serialize(serializer),
+            IdlValue::List(values) => {
+                let mut seq = serializer.serialize_seq(Some(values.len()))?;
+                for value in values {
+                    seq.serialize_element(value)?;
+                }
+                seq.end()
+            }
+            IdlValue::Struct(fields) => {
+                let mut map = serializer.serialize_map(Some(fields.len()))?;
+                for (name, value) in fields {
+                    map.serialize_entry(name, value)?;
+                }
+                map.end()
+            }
+            IdlValue::Enum {
+                variant,
+                fields: None,
+            } => serializer.serialize_str(variant),
+            IdlValue::Enum {
+                variant,
+                fields: Some(fields),
+            } => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry(variant, fields)?;
+                map.end()
+            }
+        }
+    }
+}
+
+/// Event of the `Program data:` payload, 8 bytes discriminator followed by Borsh
+/// serialized fields, `None` if the IDL has no such event or the data doesn't match its fields
+pub fn decode_event(idl: &Idl, data: &[u8]) -> Option<AnchorEvent> {
+    let event = idl.event(data)?;
+    let mut data = &data[8..];
+    let fields = idl
+        .event_fields(&event)?
+        .into_iter()
+        .map(|field| {
+            Some(EventField {
+                ty: type_name(&field.ty),
+                value: decode_value(idl, &field.ty, &mut data, 0)?,
+                name: field.name,
+            })
+        })
+        .collect::<Option<Vec<EventField>>>()?;
+    // bytes left after the fields mean the event was decoded with wrong field types
+    if !data.is_empty() {
+        return None;
+    }
+    Some(AnchorEvent {
+        name: event.name,
+        fields,
+    })
+}
+
+/// type as written in Rust, e.g. `Option<u64>` for `{ "option": "u64" }`
+fn type_name(ty: &Value) -> String {
+    match ty {
+        Value::String(name) if name == "publicKey" => String::from("pubkey"),
+        Value::String(name) => name.clone(),
+        Value::Object(object) => {
+            if let Some(inner) = object.get("option") {
+                return format!("Option<{}>", type_name(inner));
+            }
+            if let Some(inner) = object.get("vec") {
+                return format!("Vec<{}>", type_name(inner));
+            }
+            if let Some(Value::Array(array)) = object.get("array") {
+                if let [inner, len] = array.as_slice() {
+                    return format!("[{}; {}]", type_name(inner), len);
+                }
+            }
+            if let Some(name) = object.get("defined").and_then(defined_name) {
+                return name.to_string();
+            }
+            ty.to_string()
+        }
+        _ => ty.to_string(),
+    }
+}
+
+/// name of a defined type, a string in legacy IDLs and `{ "name": .. }` in new ones
+fn defined_name(defined: &Value) -> Option<&str> {
+    match defined {
+        Value::String(name) => Some(name),
+        Value::Object(object) => object.get("name")?.as_str(),
+        _ => None,
+    }
+}
+
+fn take<'a>(data: &mut &'a [u8], len: usize) -> Option<&'a [u8]> {
+    if data.len() < len {
+        return None;
+    }
+    let (head, rest) = data.split_at(len);
+    *data = rest;
+    Some(head)
+}
+
+fn take_array<const N: usize>(data: &mut &[u8]) -> Option<[u8; N]> {
+    take(data, N)?.try_into().ok()
+}
+
+/// Borsh length prefix of strings, bytes and vectors
+fn take_len(data: &mut &[u8]) -> Option<usize> {
+    Some(u32::from_le_bytes(take_array(data)?) as usize)
+}
+
+fn decode_value(idl: &Idl, ty: &Value, data: &mut &[u8], depth: usize) -> Option<IdlValue> {
+    if depth > MAX_DEPTH {
+        return None;
+    }
+    let object = match ty {
+        Value::String(name) => return decode_primitive(name, data),
+        Value::Object(object) => object,
+        _ => return None,
+    };
+    if let Some(inner) = object.get("option") {
+        return match take_array::<1>(data)?[0] {
+            0 => Some(IdlValue::Option(None)),
+            1 => {
+                let value = decode_value(idl, inner, data, depth + 1)?;
+                Some(IdlValue::Option(Some(Box::new(value))))
+            }
+            _ => None,
+        };
+    }
+    if let Some(inner) = object.get("vec") {
+        let len = take_len(data)?;
+        if inner == "u8" {
+            return Some(IdlValue::Bytes(take(data, len)?.to_vec()));
+        }
+        return decode_list(idl, inner, len, data, depth);
+    }
+    if let Some(Value::Array(array)) = object.get("array") {
+        let [inner, len] = array.as_slice() else {
+            return None;
+        };
+        return decode_list(idl, inner, len.as_u64()? as usize, data, depth);
+    }
+    let name = defined_name(object.get("defined")?)?;
+    let type_def = idl.type_def(name)?.ty;
+    match type_def.kind.as_str() {
+        "struct" => decode_fields(idl, type_def.fields.as_deref(), data, depth),
+        "enum" => {
+            let variant = type_def.variants.get(take_array::<1>(data)?[0] as usize)?;
+            let fields = match &variant.fields {
+                Some(fields) => Some(Box::new(decode_fields(idl, Some(fields), data, depth)?)),
+                None => None,
+            };
+            Some(IdlValue::Enum {
+                variant: variant.name.clone(),
+                fields,
+            })
+        }
+        "type" => decode_value(idl, type_def.alias.as_ref()?, data, depth + 1),
+        _ => None,
+    }
+}
+
+fn decode_list(
+    idl: &Idl,
+    ty: &Value,
+    len: usize,
+    data: &mut &[u8],
+    depth: usize,
+) -> Option<IdlValue> {
+    // the length comes from the data, every item takes at least a byte
+    if len > data.len() {
+        return None;
+    }
+    (0..len)
+        .map(|_| decode_value(idl, ty, data, depth + 1))
+        .collect::<Option<Vec<IdlValue>>>()
+        .map(IdlValue::List)
+}
+
+/// named fields of structs and variants decode to `Struct`, tuple fields to `List`
+fn decode_fields(
+    idl: &Idl,
+    fields: Option<&[Value]>,
+    data: &mut &[u8],
+    depth: usize,
+) -> Option<IdlValue> {
+    let fields = fields.unwrap_or_default();
+    let named: Option<Vec<IdlField>> = fields
+        .iter()
+        .map(|field| serde_json::from_value(field.clone()).ok())
+        .collect();
+    match named {
+        Some(named) => named
+            .into_iter()
+            .map(|field| {
+                let value = decode_value(idl, &field.ty, data, depth + 1)?;
+                Some((field.name, value))
+            })
+            .collect::<Option<Vec<(String, IdlValue)>>>()
+            .map(IdlValue::Struct),
+        None => fields
+            .iter()
+            .map(|ty| decode_value(idl, ty, data, depth + 1))
+            .collect::<Option<Vec<IdlValue>>>()
+            .map(IdlValue::List),
+    }
+}
+
+fn decode_primitive(name: &str, data: &mut &[u8]) -> Option<IdlValue> {
+    let value = match name {
+        "bool" => match take_array::<1>(data)?[0] {
+            0 => IdlValue::Bool(false),
+            1 => IdlValue::Bool(true),
+            _ => return None,
+        },
+        "u8" => IdlValue::Unsigned(u8::from_le_bytes(take_array(data)?) as u128),
+        "u16" => IdlValue::Unsigned(u16::from_le_bytes(take_array(data)?) as u128),
+        "u32" => IdlValue::Unsigned(u32::from_le_bytes(take_array(data)?) as u128),
+        "u64" => IdlValue::Unsigned(u64::from_le_bytes(take_array(data)?) as u128),
+        "u128" => IdlValue::Unsigned(u128::from_le_bytes(take_array(data)?)),
+        "i8" => IdlValue::Signed(i8::from_le_bytes(take_array(data)?) as i128),
+        "i16" => IdlValue::Signed(i16::from_le_bytes(take_array(data)?) as i128),
+        "i32" => IdlValue::Signed(i32::from_le_bytes(take_array(data)?) as i128),
+        "i64" => IdlValue::Signed(i64::from_le_bytes(take_array(data)?) as i128),
+        "i128" => IdlValue::Signed(i128::from_le_bytes(take_array(data)?)),
+        "f32" => IdlValue::Float(f32::from_le_bytes(take_array(data)?) as f64),
+        "f64" => IdlValue::Float(f64::from_le_bytes(take_array(data)?)),
+        "string" => {
+            let len = take_len(data)?;
+            IdlValue::String(String::from_utf8(take(data, len)?.to_vec()).ok()?)
+        }
+        "bytes" => {
+            let len = take_len(data)?;
+            IdlValue::Bytes(take(data, len)?.to_vec())
+        }
+        "publicKey" | "pubkey" => {
+            IdlValue::PublicKey(Pubkey::new_from_array(take_array(data)?).into())
+        }
+        _ => return None,
+    };
+    Some(value)
+}
@@ -0,0 +1,158 @@
+use std::{
+    collections::HashMap,
+    io::Read,
+    sync::{Arc, Mutex, OnceLock},
+};
+
+use flate2::read::ZlibDecoder;
+use serde::Deserialize;
+use serde_json::Value;
+use solana_client::client_error::ClientError;
+use solana_sdk::pubkey::Pubkey;
+
+use super::event_discriminator;
+use crate::rpc;
+
+/// IDLs fetched during the command run by program id, `None` for programs without IDL
+static IDL_CACHE: OnceLock<Mutex<HashMap<Pubkey, Option<Arc<Idl>>>>> = OnceLock::new();
+
+/// Parts of Anchor IDL used by the explorer, both legacy (< 0.30) and new IDL formats,
+/// events and types are parsed when they are looked up, so an entry the explorer
+/// can't read doesn't make the rest of the IDL unusable
+#[derive(Debug, Deserialize)]
+pub struct Idl {
+    #[serde(default)]
+    pub errors: Vec<IdlError>,
+    #[serde(default)]
+    pub events: Vec<Value>,
+    #[serde(default)]
+    pub types: Vec<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct IdlError {
+    pub code: u32,
+    pub name: String,
+    pub msg: Option<String>,
+}
+
+/// Event of the IDL, new IDLs have the discriminator and keep fields in the type
+/// of the same name, legacy IDLs have fields in the event
+#[derive(Debug, Deserialize)]
+pub struct IdlEvent {
+    pub name: String,
+    pub discriminator: Option<[u8; 8]>,
+    #[serde(default)]
+    pub fields: Vec<IdlField>,
+}
+
+impl IdlEvent {
+    pub fn discriminator(&self) -> [u8; 8] {
+        self.discriminator
+            .unwrap_or_else(|| event_discriminator(&self.name))
+    }
+}
+
+/// Field of a struct, an event or an enum variant, `ty` is kept as JSON
+/// as type notations differ between IDL versions, e.g. "publicKey" and "pubkey"
+#[derive(Clone, Debug, Deserialize)]
+pub struct IdlField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: Value,
+}
+
+/// type definition found by its name, see `Idl::type_def`
+#[derive(Debug, Deserialize)]
+pub struct IdlTypeDef {
+    #[serde(rename = "type")]
+    pub ty: IdlTypeDefTy,
+}
+
+/// Type definition body, `kind` is "struct", "enum" or "type" for aliases,
+/// struct and variant `fields` are named fields or types of tuple fields
+#[derive(Debug, Deserialize)]
+pub struct IdlTypeDefTy {
+    pub kind: String,
+    pub fields: Option<Vec<Value>>,
+    #[serde(default)]
+    pub variants: Vec<IdlEnumVariant>,
+    pub alias: Option<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct IdlEnumVariant {
+    pub name: String,
+    pub fields: Option<Vec<Value>>,
+}
+
+impl Idl {
+    pub fn error(&self, code: u32) -> Option<&IdlError> {
+        self.errors.iter().find(|error| error.code == code)
+    }
+
+    /// event the data of `Program data:` log starts with the discriminator of
+    pub fn event(&self, data: &[u8]) -> Option<IdlEvent> {
+        let discriminator = data.get(..8)?;
+        self.events
+            .iter()
+            .filter_map(|event| IdlEvent::deserialize(event).ok())
+            .find(|event| event.discriminator() == discriminator)
+    }
+
+    /// fields of the event, listed in the event by legacy IDLs
+    /// and in the type of the event name by new IDLs
+    pub fn event_fields(&self, event: &IdlEvent) -> Option<Vec<IdlField>> {
+        if event.discriminator.is_none() {
+            return Some(event.fields.clone());
+        }
+        let fields = self.type_def(&event.name)?.ty.fields?;
+        fields
+            .iter()
+            .map(|field| IdlField::deserialize(field).ok())
+            .collect()
+    }
+
+    pub fn type_def(&self, name: &str) -> Option<IdlTypeDef> {
+        let type_def = self
+            .types
+            .iter()
+            .find(|type_def| type_def.get("name").and_then(Value::as_str) == Some(name))?;
+        IdlTypeDef::deserialize(type_def).ok()
+    }
+}
+
+/// address of the account Anchor CLI stores program IDL in
+pub fn idl_address(program_id: &Pubkey) -> Pubkey {
+    let (base, _) = Pubkey::find_program_address(&[], program_id);
+    Pubkey::create_with_seed(&base, "anchor:idl", program_id).unwrap()
+}
+
+/// IDL published on-chain with `anchor idl init`, `None` if the program has no IDL account
+/// or the account can't be read, IDLs are fetched once per program during the command run
+pub fn fetch_idl(program_id: &Pubkey) -> Result<Option<Arc<Idl>>, ClientError> {
+    let cache = IDL_CACHE.get_or_init(Default::default);
+    if let Some(idl) = cache.lock().unwrap().get(program_id) {
+        return Ok(idl.clone());
+    }
+    let account = rpc::send_rate_limited(|rpc_con| {
+        rpc_con.get_account_with_commitment(&idl_address(program_id), rpc_con.commitment())
+    })?
+    .value;
+    let idl = account
+        .and_then(|account| parse_idl_account(&account.data))
+        .map(Arc::new);
+    cache.lock().unwrap().insert(*program_id, idl.clone());
+    Ok(idl)
+}
+
+fn parse_idl_account(data: &[u8]) -> Option<Idl> {
+    // 8 bytes discriminator, 32 bytes authority, u32 length of zlib compressed IDL JSON
+    let len_bytes: [u8; 4] = data.get(40..44)?.try_into().ok()?;
+    let compressed = data.get(44..44 + u32::from_le_bytes(len_bytes) as usize)?;
+    let mut json = String::new();
+    ZlibDecoder::new(compressed)
+        .read_to_string(&mut json)
+        .ok()?;
+    serde_json::from_str(&json).ok()
+}
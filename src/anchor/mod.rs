@@ -0,0 +1,23 @@
+pub mod error;
+pub mod event;
+pub mod idl;
+
+use solana_sdk::hash::hash;
+
+/// first 8 bytes of the hash of "global:<name>" the data of Anchor instruction `name` starts with
+pub fn instruction_discriminator(name: &str) -> [u8; 8] {
+    discriminator("global", name)
+}
+
+/// first 8 bytes of the hash of "event:<Name>" data of Anchor event `Name` starts with,
+/// legacy IDLs don't list event discriminators
+pub fn event_discriminator(name: &str) -> [u8; 8] {
+    discriminator("event", name)
+}
+
+fn discriminator(namespace: &str, name: &str) -> [u8; 8] {
+    let mut discriminator = [0u8; 8];
+    let preimage = format!("{}:{}", namespace, name);
+    discriminator.copy_from_slice(&hash(preimage.as_bytes()).to_bytes()[..8]);
+    discriminator
+}
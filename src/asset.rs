@@ -0,0 +1,338 @@
+use std::{process::exit, str::FromStr};
+
+use crate::{
+    account::reader::show_account_page,
+    metaplex::bubblegum::{find_asset_id, CompressedAssetId},
+    metaplex::das::{
+        Asset, AssetList, AssetPage, AssetSignature, AssetSignaturePage, AssetSummary,
+    },
+    output::{print_error, print_warning, OutputFormat},
+    page::Page,
+    rpc,
+};
+use serde_json::{json, Value};
+use solana_client::{
+    client_error::{ClientError as RpcClientError, ClientErrorKind},
+    rpc_request::{RpcError, RpcRequest},
+};
+use solana_sdk::pubkey::Pubkey;
+
+/// DAS API does not return more than 1000 assets per page
+pub const DAS_MAX_PAGE_SIZE: usize = 1000;
+
+/// Filters of `assets` command, each filter maps to a DAS search parameter
+#[derive(Debug, Default)]
+pub struct AssetsFilter {
+    pub owner: Option<String>,
+    pub collection: Option<String>,
+    pub creator: Option<String>,
+    pub authority: Option<String>,
+    pub interface: Option<String>,
+    pub compressed: Option<bool>,
+    pub burnt: Option<bool>,
+    pub frozen: Option<bool>,
+}
+
+impl AssetsFilter {
+    /// picks the most specific DAS method for the given filters, falls back to `searchAssets`
+    /// when filters are combined or search only filters are used
+    fn to_das_query(&self) -> (&'static str, Value) {
+        let search_only = self.interface.is_some()
+            || self.compressed.is_some()
+            || self.burnt.is_some()
+            || self.frozen.is_some();
+        let main_filters = [
+            self.owner.is_some(),
+            self.collection.is_some(),
+            self.creator.is_some(),
+            self.authority.is_some(),
+        ]
+        .into_iter()
+        .filter(|is_set| *is_set)
+        .count();
+
+        if !search_only && main_filters == 1 {
+            if let Some(owner) = &self.owner {
+                return ("getAssetsByOwner", json!({ "ownerAddress": owner }));
+            }
+            if let Some(collection) = &self.collection {
+                return (
+                    "getAssetsByGroup",
+                    json!({ "groupKey": "collection", "groupValue": collection }),
+                );
+            }
+            if let Some(creator) = &self.creator {
+                return ("getAssetsByCreator", json!({ "creatorAddress": creator }));
+            }
+            if let Some(authority) = &self.authority {
+                return (
+                    "getAssetsByAuthority",
+                    json!({ "authorityAddress": authority }),
+                );
+            }
+        }
+
+        let mut params = json!({});
+        if let Some(owner) = &self.owner {
+            params["ownerAddress"] = json!(owner);
+        }
+        if let Some(collection) = &self.collection {
+            params["grouping"] = json!(["collection", collection]);
+        }
+        if let Some(creator) = &self.creator {
+            params["creatorAddress"] = json!(creator);
+        }
+        if let Some(authority) = &self.authority {
+            params["authorityAddress"] = json!(authority);
+        }
+        if let Some(interface) = &self.interface {
+            params["interface"] = json!(interface);
+        }
+        if let Some(compressed) = self.compressed {
+            params["compressed"] = json!(compressed);
+        }
+        if let Some(burnt) = self.burnt {
+            params["burnt"] = json!(burnt);
+        }
+        if let Some(frozen) = self.frozen {
+            params["frozen"] = json!(frozen);
+        }
+        ("searchAssets", params)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.owner.is_none()
+            && self.collection.is_none()
+            && self.creator.is_none()
+            && self.authority.is_none()
+            && self.interface.is_none()
+            && self.compressed.is_none()
+            && self.burnt.is_none()
+            && self.frozen.is_none()
+    }
+
+    fn addresses(&self) -> impl Iterator<Item = &String> {
+        [&self.owner, &self.collection, &self.creator, &self.authority]
+            .into_iter()
+            .flatten()
+    }
+}
+
+/// Where to start listing assets from, DAS supports both page and cursor based pagination
+#[derive(Debug)]
+pub struct AssetsPagination {
+    pub limit: usize,
+    pub page: Option<u32>,
+    pub cursor: Option<String>,
+}
+
+/// Main entry point to assets command
+pub fn list_assets(
+    filter: &AssetsFilter,
+    pagination: &AssetsPagination,
+    output_format: OutputFormat,
+) {
+    if filter.is_empty() {
+        print_warning("at least one filter is required, e.g. --owner <ADDRESS>");
+        exit(1);
+    }
+    if let Some(address) = filter.addresses().find(|a| a.parse::<Pubkey>().is_err()) {
+        print_warning(format!("address {:?} is not a valid Solana public key", address).as_str());
+        exit(1);
+    }
+
+    let (method, query) = filter.to_das_query();
+    let mut page_num = pagination.page.unwrap_or(1);
+    let mut cursor = pagination.cursor.clone();
+    // NDJSON lines are printed as DAS pages are fetched, other formats show the whole list
+    let is_streamed = matches!(output_format, OutputFormat::AsNdJson);
+    let mut summaries: Vec<AssetSummary> = Vec::new();
+    let mut listed = 0;
+
+    // DAS offsets numbered pages by the page size, it can't change between pages
+    let page_size = pagination.limit.clamp(1, DAS_MAX_PAGE_SIZE);
+    while listed < pagination.limit {
+        let mut params = query.clone();
+        params["limit"] = json!(page_size);
+        match &cursor {
+            Some(cursor) => params["cursor"] = json!(cursor),
+            None => params["page"] = json!(page_num),
+        }
+
+        let res = match search_das_assets(method, params) {
+            Ok(res) => res,
+            Err(err) => {
+                print_error(err);
+                exit(1);
+            }
+        };
+        let fetched = res.items.len();
+        let items = &res.items[..fetched.min(pagination.limit - listed)];
+        listed += items.len();
+        for asset in items {
+            let summary = AssetSummary::from(asset);
+            if is_streamed {
+                let mut page = Page::new(output_format.clone());
+                page.add(summary);
+                page.display();
+            } else {
+                summaries.push(summary);
+            }
+        }
+        if fetched < page_size {
+            break;
+        }
+        match (&cursor, res.cursor) {
+            (Some(_), Some(next_cursor)) => cursor = Some(next_cursor),
+            (Some(_), None) => break,
+            (None, _) => page_num += 1,
+        }
+    }
+
+    if is_streamed {
+        return;
+    }
+    let mut page = Page::new(output_format);
+    page.add(AssetList {
+        total: summaries.len(),
+        items: summaries,
+    });
+    page.display();
+}
+
+/// Main entry point to cnft-id command, derives compressed asset id from merkle tree and leaf index
+/// without any RPC calls
+pub fn derive_cnft_id(tree: &str, leaf_index: u64, show_asset: bool, output_format: OutputFormat) {
+    let tree_pubkey = match Pubkey::from_str(tree) {
+        Ok(pubkey) => pubkey,
+        Err(_) => {
+            print_warning(format!("address {:?} is not a valid Solana public key", tree).as_str());
+            exit(1);
+        }
+    };
+
+    let mut page = Page::new(output_format);
+    page.add(CompressedAssetId::new(&tree_pubkey, leaf_index));
+    if show_asset {
+        let asset_id = find_asset_id(&tree_pubkey, leaf_index);
+        show_account_page(&asset_id.to_string(), page, false);
+    } else {
+        page.display();
+    }
+}
+
+/// Finds merkle tree and leaf index (nonce) of a compressed asset id with DAS API,
+/// asset id is a PDA and can't be decoded offline
+pub fn decode_cnft_id(address: &str, show_asset: bool, output_format: OutputFormat) {
+    let asset_pubkey = match Pubkey::from_str(address) {
+        Ok(pubkey) => pubkey,
+        Err(_) => {
+            print_warning(
+                format!("address {:?} is not a valid Solana public key", address).as_str(),
+            );
+            exit(1);
+        }
+    };
+    let asset = match get_das_asset(&asset_pubkey) {
+        Ok(asset) => asset,
+        Err(err) => {
+            print_error(err);
+            exit(1);
+        }
+    };
+    if !asset.compression.compressed {
+        print_warning(format!("asset {} is not compressed", asset_pubkey).as_str());
+        exit(1);
+    }
+    let tree_pubkey = match Pubkey::from_str(&asset.compression.tree) {
+        Ok(pubkey) => pubkey,
+        Err(_) => {
+            print_warning(
+                format!(
+                    "DAS API returned invalid merkle tree for asset {}",
+                    asset_pubkey
+                )
+                .as_str(),
+            );
+            exit(1);
+        }
+    };
+    let leaf_index = asset.compression.leaf_id as u64;
+    if find_asset_id(&tree_pubkey, leaf_index) != asset_pubkey {
+        print_warning(
+            format!(
+                "asset id {} does not match Bubblegum PDA of tree {} and leaf index {}",
+                asset_pubkey, tree_pubkey, leaf_index
+            )
+            .as_str(),
+        );
+    }
+
+    let mut page = Page::new(output_format);
+    page.add(CompressedAssetId::new(&tree_pubkey, leaf_index));
+    if show_asset {
+        show_account_page(address, page, false);
+    } else {
+        page.display();
+    }
+}
+
+pub fn get_das_asset(pubkey: &Pubkey) -> Result<Asset, RpcClientError> {
+    let rpc_con = rpc::init_connection();
+    let res = rpc_con.send::<Asset>(
+        RpcRequest::Custom { method: "getAsset" },
+        json!([pubkey.to_string()]),
+    );
+    exit_if_das_unsupported(res)
+}
+
+/// DAS asset shown as a part of other pages, `None` when the RPC does not support DAS API
+/// or does not know the asset
+pub fn find_das_asset(pubkey: &Pubkey) -> Option<Asset> {
+    rpc::send_rate_limited(|rpc_con| {
+        rpc_con.send::<Asset>(
+            RpcRequest::Custom { method: "getAsset" },
+            json!([pubkey.to_string()]),
+        )
+    })
+    .ok()
+}
+
+/// Page of transaction signatures of a compressed asset from the newest, compressed
+/// assets have no account so `getSignaturesForAddress` can't be used to find its history.
+/// Pages are numbered from 1 and are at most `DAS_MAX_PAGE_SIZE` long
+pub fn get_das_asset_signatures(
+    pubkey: &Pubkey,
+    page_num: usize,
+    page_size: usize,
+) -> Result<Vec<AssetSignature>, RpcClientError> {
+    let res: AssetSignaturePage = exit_if_das_unsupported(rpc::send_with_named_params(
+        "getSignaturesForAsset",
+        json!({ "id": pubkey.to_string(), "page": page_num, "limit": page_size }),
+    ))?;
+    Ok(res
+        .items
+        .into_iter()
+        .map(|(signature, operation)| AssetSignature {
+            signature,
+            operation,
+        })
+        .collect())
+}
+
+fn search_das_assets(method: &'static str, params: Value) -> Result<AssetPage, RpcClientError> {
+    exit_if_das_unsupported(rpc::send_with_named_params(method, params))
+}
+
+fn exit_if_das_unsupported<T>(res: Result<T, RpcClientError>) -> Result<T, RpcClientError> {
+    match res {
+        Err(err) => match err.kind() {
+            ClientErrorKind::RpcError(RpcError::RpcResponseError { code: -32601, .. }) => {
+                println!("RPC does not support DAS API");
+                exit(1);
+            }
+            _ => Err(err),
+        },
+        _ => res,
+    }
+}
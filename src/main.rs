@@ -1,4 +1,9 @@
+// solana_client::client_error::ClientError is large, but it is returned as is from RPC calls
+#![allow(clippy::result_large_err)]
+
 mod account;
+mod anchor;
+mod asset;
 mod balance;
 mod magiceden;
 mod metaplex;
@@ -10,9 +15,20 @@ mod transaction;
 mod pretty;
 
 use account::reader::read_account;
+use asset::{decode_cnft_id, derive_cnft_id, list_assets, AssetsFilter, AssetsPagination};
 use clap::{Args, Parser, Subcommand};
 use output::OutputFormat;
-use transaction::{list_account_txs, read_tx};
+use pretty::time::parse_timestamp;
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use std::str::FromStr;
+use transaction::{
+    balance_history::show_balance_history,
+    decoder::decode_tx,
+    filter::TransactionsFilter,
+    reader::{list_account_txs, read_tx, TransactionsPagination},
+    simulator::simulate_tx,
+    transfer::list_transfers,
+};
 
 /// Solana explorer CLI utility
 /// with a goal to explore all account and tx on Solana
@@ -21,6 +37,15 @@ use transaction::{list_account_txs, read_tx};
 struct Cli {
     #[command(subcommand)]
     command: Resource,
+    /// IPFS gateway to resolve ipfs:// URIs and CIDs with, defaults to SE_IPFS_GATEWAY env var or https://ipfs.io
+    #[arg(long, global = true)]
+    gateway: Option<String>,
+    /// Arweave gateway to resolve ar:// URIs with, defaults to SE_ARWEAVE_GATEWAY env var or https://arweave.net
+    #[arg(long, global = true)]
+    arweave_gateway: Option<String>,
+    /// max RPC requests per second when fetching many transactions, defaults to SE_RPC_RATE_LIMIT env var or 40
+    #[arg(long, global = true)]
+    rate_limit: Option<u32>,
 }
 
 #[derive(Subcommand)]
@@ -37,6 +62,14 @@ enum Resource {
     AccountTransactions(ListAccountTransactionsCommand),
     /// alias for account-transactions command
     AcTxs(ListAccountTransactionsCommand),
+    /// show how SOL or token balance of an address changed with its transactions
+    BalanceHistory(BalanceHistoryCommand),
+    /// list SOL and token transfers of a wallet and its token accounts
+    Transfers(TransfersCommand),
+    /// search digital assets (NFTs, cNFTs, Core assets) with DAS API
+    Assets(AssetsCommand),
+    /// derive compressed NFT asset id from merkle tree and leaf index or the other way around
+    CnftId(CnftIdCommand),
 }
 
 #[derive(Args, Debug)]
@@ -45,35 +78,372 @@ struct AccountCommand {
     address: String,
     #[arg(short, long)]
     format: Option<OutputFormat>,
+    /// fetch and validate off-chain metadata JSON of NFTs and tokens
+    #[arg(long)]
+    offchain: bool,
 }
 
 #[derive(Args, Debug)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct TransactionCommand {
+    #[command(subcommand)]
+    command: Option<TransactionSubcommand>,
     /// hash of transaction signature
-    signature: String,
+    #[arg(required = true)]
+    signature: Option<String>,
+    #[arg(short, long)]
+    format: Option<OutputFormat>,
+}
+
+#[derive(Subcommand, Debug)]
+enum TransactionSubcommand {
+    /// decode serialized transaction offline
+    Decode(DecodeTransactionCommand),
+    /// simulate serialized transaction without signature verification and with the latest blockhash
+    Simulate(SimulateTransactionCommand),
+}
+
+#[derive(Args, Debug)]
+struct DecodeTransactionCommand {
+    /// base64 or base58 encoded transaction or a file with it
+    transaction: String,
+    /// resolve accounts loaded from address lookup tables over RPC
+    #[arg(long)]
+    resolve_lookup_tables: bool,
+    #[arg(short, long)]
+    format: Option<OutputFormat>,
+}
+
+#[derive(Args, Debug)]
+struct SimulateTransactionCommand {
+    /// base64 or base58 encoded transaction or a file with it
+    transaction: String,
+    #[arg(short, long)]
+    format: Option<OutputFormat>,
 }
 
 #[derive(Args, Debug)]
 struct ListAccountTransactionsCommand {
     /// public account address
     address: String,
+    /// max number of transactions to list
+    #[arg(short, long, default_value_t = 100)]
+    limit: usize,
+    /// list transactions older than this signature
+    #[arg(long)]
+    before: Option<String>,
+    /// stop listing at this signature (not listed itself) or list transactions before this
+    /// block time, unix timestamp, date (YYYY-MM-DD) or RFC 3339 time
+    #[arg(long, value_parser = parse_until)]
+    until: Option<Until>,
+    /// list transactions since this block time, unix timestamp, date or RFC 3339 time
+    #[arg(long, value_parser = parse_block_time)]
+    since: Option<i64>,
+    /// transactions that invoke the program at any depth
+    #[arg(long)]
+    program: Option<Pubkey>,
+    /// only failed transactions
+    #[arg(long, conflicts_with = "succeeded")]
+    failed: bool,
+    /// only succeeded transactions
+    #[arg(long)]
+    succeeded: bool,
+    /// min absolute SOL balance change of the address, not supported for compressed NFTs
+    #[arg(long)]
+    min_sol_change: Option<f64>,
+    /// page through the whole account history, ignores the limit
+    #[arg(long, conflicts_with = "limit")]
+    all: bool,
+    /// fetch and show transactions in full instead of a signature table
+    #[arg(long)]
+    details: bool,
+    /// max number of transactions fetched at once
+    #[arg(long, default_value_t = 8, value_parser = clap::value_parser!(u16).range(1..))]
+    concurrency: u16,
+    #[arg(short, long)]
+    format: Option<OutputFormat>,
+}
+
+#[derive(Args, Debug)]
+struct BalanceHistoryCommand {
+    /// public account address
+    address: String,
+    /// token balance of the mint in the associated token account of the address instead of SOL,
+    /// other token accounts of the address are not tracked
+    #[arg(long)]
+    mint: Option<Pubkey>,
+    /// max number of balance changes to show
+    #[arg(short, long, default_value_t = 100)]
+    limit: usize,
+    /// show changes older than this signature
+    #[arg(long)]
+    before: Option<String>,
+    /// stop at this signature (not shown itself)
+    #[arg(long)]
+    until: Option<String>,
+    /// go through the whole account history, ignores the limit
+    #[arg(long, conflicts_with = "limit")]
+    all: bool,
+    /// max number of transactions fetched at once
+    #[arg(long, default_value_t = 8, value_parser = clap::value_parser!(u16).range(1..))]
+    concurrency: u16,
+    /// print CSV rows (slot, time, signature, delta, balance) instead of the table
+    #[arg(long, conflicts_with = "format")]
+    csv: bool,
+    #[arg(short, long)]
+    format: Option<OutputFormat>,
+}
+
+#[derive(Args, Debug)]
+struct TransfersCommand {
+    /// wallet address
+    address: String,
+    /// max number of transactions with transfers to list
+    #[arg(short, long, default_value_t = 100)]
+    limit: usize,
+    /// list transfers older than this signature
+    #[arg(long)]
+    before: Option<String>,
+    /// stop listing at this signature (not listed itself)
+    #[arg(long)]
+    until: Option<String>,
+    /// go through the whole wallet history, ignores the limit
+    #[arg(long, conflicts_with = "limit")]
+    all: bool,
+    /// max number of transactions fetched at once
+    #[arg(long, default_value_t = 8, value_parser = clap::value_parser!(u16).range(1..))]
+    concurrency: u16,
+    /// print CSV rows (date, signature, direction, counterparty, mint, symbol, amount, fee)
+    /// instead of the table
+    #[arg(long, conflicts_with = "format")]
+    csv: bool,
+    #[arg(short, long)]
+    format: Option<OutputFormat>,
+}
+
+/// `--until` of account-transactions command, a signature cursor or a block time
+#[derive(Clone, Debug)]
+enum Until {
+    Signature(String),
+    Time(i64),
+}
+
+fn parse_until(value: &str) -> Result<Until, String> {
+    match Signature::from_str(value) {
+        Ok(_) => Ok(Until::Signature(value.to_string())),
+        Err(_) => parse_block_time(value)
+            .map(Until::Time)
+            .map_err(|_| format!("{:?} is not a signature or a block time", value)),
+    }
+}
+
+fn parse_block_time(value: &str) -> Result<i64, String> {
+    parse_timestamp(value).ok_or_else(|| {
+        format!(
+            "{:?} is not a unix timestamp, date (YYYY-MM-DD) or RFC 3339 time",
+            value
+        )
+    })
+}
+
+#[derive(Args, Debug)]
+struct AssetsCommand {
+    /// assets owned by the address
+    #[arg(long)]
+    owner: Option<String>,
+    /// assets that belong to the collection address
+    #[arg(long)]
+    collection: Option<String>,
+    /// assets created by the address
+    #[arg(long)]
+    creator: Option<String>,
+    /// assets with the update authority address
+    #[arg(long)]
+    authority: Option<String>,
+    /// asset interface, e.g. V1_NFT, ProgrammableNFT, MplCoreAsset
+    #[arg(long)]
+    interface: Option<String>,
+    /// only compressed (true) or only uncompressed (false) assets
+    #[arg(long)]
+    compressed: Option<bool>,
+    /// only burnt (true) or only not burnt (false) assets
+    #[arg(long)]
+    burnt: Option<bool>,
+    /// only frozen (true) or only not frozen (false) assets
+    #[arg(long)]
+    frozen: Option<bool>,
+    /// max number of assets to list
+    #[arg(short, long, default_value_t = 100)]
+    limit: usize,
+    /// DAS page to start listing from, pages are as long as the limit, up to 1000 assets
+    #[arg(long, conflicts_with = "cursor")]
+    page: Option<u32>,
+    /// DAS cursor to start listing from
+    #[arg(long)]
+    cursor: Option<String>,
+    #[arg(short, long)]
+    format: Option<OutputFormat>,
+}
+
+#[derive(Args, Debug)]
+struct CnftIdCommand {
+    /// compressed NFT asset id to find merkle tree and leaf index of (requires DAS API)
+    #[arg(conflicts_with = "tree", required_unless_present = "tree")]
+    asset: Option<String>,
+    /// merkle tree address
+    #[arg(long, requires = "leaf")]
+    tree: Option<String>,
+    /// leaf index (nonce) in the merkle tree
+    #[arg(long, requires = "tree")]
+    leaf: Option<u64>,
+    /// show the asset account after the id
+    #[arg(long)]
+    show: bool,
+    #[arg(short, long)]
+    format: Option<OutputFormat>,
 }
 
 fn main() {
     let cli = Cli::parse();
+    if let Some(gateway) = &cli.gateway {
+        pretty::uri::set_ipfs_gateway(gateway.clone());
+    }
+    if let Some(gateway) = &cli.arweave_gateway {
+        pretty::uri::set_arweave_gateway(gateway.clone());
+    }
+    if let Some(rate_limit) = cli.rate_limit {
+        rpc::set_rate_limit(rate_limit);
+    }
 
     match &cli.command {
         Resource::Account(args) | Resource::Ac(args) => {
             read_account(
                 &args.address,
                 args.format.clone().unwrap_or(OutputFormat::AsStruct),
+                args.offchain,
             );
         }
         Resource::Transaction(args) | Resource::Tx(args) => {
-            read_tx(&args.signature);
+            match (&args.command, &args.signature) {
+                (Some(TransactionSubcommand::Decode(decode_args)), _) => {
+                    decode_tx(
+                        &decode_args.transaction,
+                        decode_args.resolve_lookup_tables,
+                        decode_args.format.clone().unwrap_or(OutputFormat::AsStruct),
+                    );
+                }
+                (Some(TransactionSubcommand::Simulate(simulate_args)), _) => {
+                    simulate_tx(
+                        &simulate_args.transaction,
+                        simulate_args
+                            .format
+                            .clone()
+                            .unwrap_or(OutputFormat::AsStruct),
+                    );
+                }
+                (None, Some(signature)) => {
+                    read_tx(
+                        signature,
+                        args.format.clone().unwrap_or(OutputFormat::AsStruct),
+                    );
+                }
+                // signature is required by clap when there is no subcommand
+                (None, None) => unreachable!(),
+            }
         }
         Resource::AccountTransactions(args) | Resource::AcTxs(args) => {
-            list_account_txs(&args.address);
+            let pagination = TransactionsPagination {
+                limit: args.limit,
+                before: args.before.clone(),
+                until: match &args.until {
+                    Some(Until::Signature(signature)) => Some(signature.clone()),
+                    _ => None,
+                },
+                all: args.all,
+            };
+            let filter = TransactionsFilter {
+                program: args.program,
+                succeeded: match (args.succeeded, args.failed) {
+                    (true, _) => Some(true),
+                    (_, true) => Some(false),
+                    _ => None,
+                },
+                since: args.since,
+                until: match &args.until {
+                    Some(Until::Time(time)) => Some(*time),
+                    _ => None,
+                },
+                min_sol_change: args.min_sol_change,
+            };
+            list_account_txs(
+                &args.address,
+                &pagination,
+                &filter,
+                args.details,
+                args.concurrency as usize,
+                args.format.clone().unwrap_or(OutputFormat::AsStruct),
+            );
+        }
+        Resource::BalanceHistory(args) => {
+            let pagination = TransactionsPagination {
+                limit: args.limit,
+                before: args.before.clone(),
+                until: args.until.clone(),
+                all: args.all,
+            };
+            show_balance_history(
+                &args.address,
+                args.mint,
+                &pagination,
+                args.concurrency as usize,
+                args.csv,
+                args.format.clone().unwrap_or(OutputFormat::AsStruct),
+            );
+        }
+        Resource::Transfers(args) => {
+            let pagination = TransactionsPagination {
+                limit: args.limit,
+                before: args.before.clone(),
+                until: args.until.clone(),
+                all: args.all,
+            };
+            list_transfers(
+                &args.address,
+                &pagination,
+                args.concurrency as usize,
+                args.csv,
+                args.format.clone().unwrap_or(OutputFormat::AsStruct),
+            );
+        }
+        Resource::Assets(args) => {
+            let filter = AssetsFilter {
+                owner: args.owner.clone(),
+                collection: args.collection.clone(),
+                creator: args.creator.clone(),
+                authority: args.authority.clone(),
+                interface: args.interface.clone(),
+                compressed: args.compressed,
+                burnt: args.burnt,
+                frozen: args.frozen,
+            };
+            let pagination = AssetsPagination {
+                limit: args.limit,
+                page: args.page,
+                cursor: args.cursor.clone(),
+            };
+            list_assets(
+                &filter,
+                &pagination,
+                args.format.clone().unwrap_or(OutputFormat::AsStruct),
+            );
+        }
+        Resource::CnftId(args) => {
+            let output_format = args.format.clone().unwrap_or(OutputFormat::AsStruct);
+            match (&args.asset, &args.tree, args.leaf) {
+                (_, Some(tree), Some(leaf)) => derive_cnft_id(tree, leaf, args.show, output_format),
+                (Some(asset), _, _) => decode_cnft_id(asset, args.show, output_format),
+                _ => unreachable!("clap requires either asset or tree and leaf"),
+            }
         }
     }
 }
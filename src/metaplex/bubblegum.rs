@@ -0,0 +1,67 @@
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{output::Output, pretty::public_key::PrettyPublicKey};
+
+pub const BUBBLEGUM_ID: Pubkey =
+    solana_sdk::pubkey::Pubkey::from_str_const("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY");
+
+/// Bubblegum logs leaf schema of minted and changed cNFTs with SPL Noop program
+pub const NOOP_ID: Pubkey =
+    solana_sdk::pubkey::Pubkey::from_str_const("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV");
+
+/// Compressed NFT (cNFT) asset id is a Bubblegum PDA of merkle tree and leaf index (nonce)
+/// source https://github.com/metaplex-foundation/mpl-bubblegum/blob/main/programs/bubblegum/program/src/utils.rs
+pub fn find_asset_id(tree: &Pubkey, leaf_index: u64) -> Pubkey {
+    let (asset_id, _) = Pubkey::find_program_address(
+        &[b"asset", tree.as_ref(), &leaf_index.to_le_bytes()],
+        &BUBBLEGUM_ID,
+    );
+    asset_id
+}
+
+/// Asset id from the data of Noop instruction invoked by Bubblegum. The data is
+/// `AccountCompressionEvent::ApplicationData` with a leaf schema event, the asset id
+/// is the first field of every leaf schema version
+/// source https://github.com/metaplex-foundation/mpl-bubblegum/blob/main/programs/bubblegum/program/src/state/leaf_schema.rs
+pub fn leaf_schema_asset_id(noop_data: &[u8]) -> Option<Pubkey> {
+    // ApplicationData event V1, length of application data, LeafSchemaEvent event type
+    if noop_data.first() != Some(&1) || noop_data.get(1) != Some(&0) || noop_data.get(6) != Some(&1)
+    {
+        return None;
+    }
+    // event version and leaf schema version precede the asset id
+    let asset_id: [u8; 32] = noop_data.get(9..41)?.try_into().ok()?;
+    Some(Pubkey::new_from_array(asset_id))
+}
+
+#[derive(Debug, Serialize)]
+pub struct CompressedAssetId {
+    pub asset_id: PrettyPublicKey,
+    pub tree: PrettyPublicKey,
+    pub leaf_index: u64,
+}
+
+impl CompressedAssetId {
+    pub fn new(tree: &Pubkey, leaf_index: u64) -> Self {
+        CompressedAssetId {
+            asset_id: find_asset_id(tree, leaf_index).into(),
+            tree: (*tree).into(),
+            leaf_index,
+        }
+    }
+}
+
+impl Output for CompressedAssetId {
+    fn struct_name(&self) -> String {
+        String::from("CompressedAssetId")
+    }
+
+    fn to_raw_struct(&self) -> String {
+        format!("{:#?}", self)
+    }
+
+    fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
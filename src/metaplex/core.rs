@@ -5,14 +5,17 @@ use mpl_core::{
 use serde::Serialize;
 use solana_sdk::pubkey::Pubkey;
 
-use crate::{output::Output, pretty::{public_key::PrettyPublicKey, string::PrettyString}};
+use crate::{
+    output::Output,
+    pretty::{public_key::PrettyPublicKey, string::PrettyString, uri::PrettyUri},
+};
 
 #[derive(Debug, Serialize)]
 pub struct CoreCollectionV1 {
     pub key: Key,
     pub update_authority: Pubkey,
     pub name: PrettyString,
-    pub uri: PrettyString,
+    pub uri: PrettyUri,
     pub num_minted: u32,
     pub current_size: u32,
 }
@@ -50,7 +53,7 @@ pub struct CoreAssetV1 {
     pub owner: PrettyPublicKey,
     pub update_authority: UpdateAuthority,
     pub name: PrettyString,
-    pub uri: PrettyString,
+    pub uri: PrettyUri,
     pub seq: Option<u64>,
 }
 
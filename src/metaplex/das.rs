@@ -1,31 +1,45 @@
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 
-use crate::output::Output;
+use crate::{output::Output, pretty::uri::PrettyUri};
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Asset {
+    pub interface: String,
+    pub id: String,
     pub content: Content,
     pub authorities: Vec<Authority>,
     pub compression: Compression,
+    #[serde(default)]
+    pub grouping: Vec<Group>,
+}
+
+impl Asset {
+    pub fn collection(&self) -> Option<&str> {
+        self.grouping
+            .iter()
+            .find(|group| group.group_key == "collection")
+            .and_then(|group| group.group_value.as_deref())
+    }
 }
 
 impl Output for Asset {
-    fn struct_name(self: &Self) -> String {
+    fn struct_name(&self) -> String {
         String::from("Asset")
     }
 
-    fn to_raw_struct(self: &Self) -> String {
+    fn to_raw_struct(&self) -> String {
         format!("{:#?}", self)
     }
 
-    fn to_json(self: &Self) -> String {
+    fn to_json(&self) -> String {
         serde_json::to_string_pretty(self).unwrap()
     }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Content {
+    pub json_uri: Option<PrettyUri>,
     pub metadata: Metadata,
     pub edition_num: Option<u64>,
     pub files: Option<Vec<AssetFile>>,
@@ -40,13 +54,13 @@ pub struct Authority {
 
 #[derive(Deserialize, Serialize, Debug)]
 pub struct Links {
-    pub image: Option<String>,
+    pub image: Option<PrettyUri>,
     pub external_url: Option<String>,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
 pub struct AssetFile {
-    pub uri: String,
+    pub uri: PrettyUri,
     pub mime: String,
 }
 
@@ -65,6 +79,12 @@ pub struct Attribute {
     pub trait_type: String,
 }
 
+#[derive(Deserialize, Serialize, Debug)]
+pub struct Group {
+    pub group_key: String,
+    pub group_value: Option<String>,
+}
+
 #[derive(Deserialize, Serialize, Debug)]
 pub struct Compression {
     pub eligible: bool,
@@ -77,6 +97,102 @@ pub struct Compression {
     pub leaf_id: u128,
 }
 
+/// A page of assets returned by DAS search methods such as `getAssetsByOwner` and `searchAssets`
+#[derive(Deserialize, Debug)]
+pub struct AssetPage {
+    pub cursor: Option<String>,
+    pub items: Vec<Asset>,
+}
+
+/// A page of transaction signatures returned by DAS `getSignaturesForAsset`,
+/// each item is a (signature, operation type) pair e.g. ("5Nq...", "Transfer")
+#[derive(Deserialize, Debug)]
+pub struct AssetSignaturePage {
+    pub items: Vec<(String, String)>,
+}
+
+/// A transaction signature of a compressed asset and the operation it performed
+/// (mint, transfer, burn, redeem etc)
+#[derive(Debug, Serialize)]
+pub struct AssetSignature {
+    pub signature: String,
+    pub operation: String,
+}
+
+impl Output for AssetSignature {
+    fn struct_name(&self) -> String {
+        String::from("AssetSignature")
+    }
+
+    fn to_raw_struct(&self) -> String {
+        format!("{:#?}", self)
+    }
+
+    fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    fn is_repeatable(&self) -> bool {
+        true
+    }
+}
+
+/// Compact view of an asset used in asset listings
+#[derive(Debug, Serialize)]
+pub struct AssetSummary {
+    pub id: String,
+    pub name: String,
+    pub interface: String,
+    pub compressed: bool,
+    pub collection: Option<String>,
+}
+
+impl From<&Asset> for AssetSummary {
+    fn from(asset: &Asset) -> Self {
+        AssetSummary {
+            id: asset.id.clone(),
+            name: asset.content.metadata.name.clone(),
+            interface: asset.interface.clone(),
+            compressed: asset.compression.compressed,
+            collection: asset.collection().map(String::from),
+        }
+    }
+}
+
+impl Output for AssetSummary {
+    fn struct_name(&self) -> String {
+        String::from("AssetSummary")
+    }
+
+    fn to_raw_struct(&self) -> String {
+        format!("{:#?}", self)
+    }
+
+    fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct AssetList {
+    pub total: usize,
+    pub items: Vec<AssetSummary>,
+}
+
+impl Output for AssetList {
+    fn struct_name(&self) -> String {
+        String::from("AssetList")
+    }
+
+    fn to_raw_struct(&self) -> String {
+        format!("{:#?}", self)
+    }
+
+    fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
 // Example of DAS API response
 //
 // {
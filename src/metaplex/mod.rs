@@ -1,2 +1,4 @@
+pub mod bubblegum;
 pub mod core;
 pub mod das;
+pub mod offchain;
@@ -0,0 +1,212 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{output::Output, pretty::uri::PrettyUri};
+
+/// Off-chain metadata JSON as described in Metaplex token standard
+/// source https://developers.metaplex.com/token-metadata/token-standard
+#[derive(Debug, Deserialize)]
+pub struct OffchainJson {
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub description: Option<String>,
+    pub image: Option<PrettyUri>,
+    pub animation_url: Option<PrettyUri>,
+    pub external_url: Option<String>,
+    pub attributes: Option<Vec<OffchainAttribute>>,
+    pub properties: Option<OffchainProperties>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct OffchainAttribute {
+    pub trait_type: Option<String>,
+    pub value: Value,
+}
+
+/// files and creators are read entry by entry, see `OffchainFile::parse` and `OffchainCreator::parse`
+#[derive(Debug, Deserialize)]
+pub struct OffchainProperties {
+    pub category: Option<String>,
+    pub files: Option<Vec<Value>>,
+    pub creators: Option<Vec<Value>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OffchainFile {
+    pub uri: PrettyUri,
+    #[serde(rename = "type")]
+    pub mime: Option<String>,
+}
+
+impl OffchainFile {
+    /// file entry with a string `uri`, invalid fields are reported to `warnings`
+    fn parse(idx: usize, entry: &Value, warnings: &mut Vec<String>) -> Option<Self> {
+        let uri = match entry.get("uri") {
+            Some(Value::String(uri)) => PrettyUri::from(uri.clone()),
+            _ => {
+                warnings.push(format!(
+                    "properties.files[{}].uri is missing or not a string",
+                    idx
+                ));
+                return None;
+            }
+        };
+        let mime = match entry.get("type") {
+            None | Some(Value::Null) => None,
+            Some(Value::String(mime)) => Some(mime.clone()),
+            Some(_) => {
+                warnings.push(format!("properties.files[{}].type is not a string", idx));
+                None
+            }
+        };
+        Some(OffchainFile { uri, mime })
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct OffchainCreator {
+    pub address: String,
+    pub share: Option<u8>,
+}
+
+impl OffchainCreator {
+    /// creator entry with a string `address`, invalid fields are reported to `warnings`
+    fn parse(idx: usize, entry: &Value, warnings: &mut Vec<String>) -> Option<Self> {
+        let address = match entry.get("address") {
+            Some(Value::String(address)) => address.clone(),
+            _ => {
+                warnings.push(format!(
+                    "properties.creators[{}].address is missing or not a string",
+                    idx
+                ));
+                return None;
+            }
+        };
+        let share = match entry.get("share") {
+            None => {
+                warnings.push(format!("properties.creators[{}].share is missing", idx));
+                None
+            }
+            Some(share) => {
+                let share = share.as_u64().and_then(|share| u8::try_from(share).ok());
+                if share.is_none() {
+                    warnings.push(format!(
+                        "properties.creators[{}].share is not a number from 0 to 255",
+                        idx
+                    ));
+                }
+                share
+            }
+        };
+        Some(OffchainCreator { address, share })
+    }
+}
+
+/// On-chain name and symbol to check off-chain metadata against,
+/// `symbol` is `None` for assets without on-chain symbol (MPL Core)
+pub struct OnchainNames<'a> {
+    pub name: &'a str,
+    pub symbol: Option<&'a str>,
+    pub is_nft: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OffchainMetadata {
+    pub uri: PrettyUri,
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub description: Option<String>,
+    pub image: Option<PrettyUri>,
+    pub animation_url: Option<PrettyUri>,
+    pub external_url: Option<String>,
+    pub category: Option<String>,
+    pub attributes: Vec<OffchainAttribute>,
+    pub files: Vec<OffchainFile>,
+    pub creators: Vec<OffchainCreator>,
+    /// mismatches with the token standard and on-chain metadata
+    pub warnings: Vec<String>,
+}
+
+impl OffchainMetadata {
+    pub fn new(uri: PrettyUri, json: OffchainJson, onchain: &OnchainNames) -> Self {
+        let mut warnings = Vec::new();
+
+        match &json.name {
+            None => warnings.push(String::from("missing name")),
+            Some(name) if name != onchain.name => warnings.push(format!(
+                "name {:?} does not match on-chain name {:?}",
+                name, onchain.name
+            )),
+            _ => {}
+        }
+        if let (Some(symbol), Some(onchain_symbol)) = (&json.symbol, onchain.symbol) {
+            if symbol != onchain_symbol {
+                warnings.push(format!(
+                    "symbol {:?} does not match on-chain symbol {:?}",
+                    symbol, onchain_symbol
+                ));
+            }
+        }
+        if json.image.is_none() {
+            warnings.push(String::from("missing image"));
+        }
+
+        let (category, files, creators) = match json.properties {
+            Some(properties) => (properties.category, properties.files, properties.creators),
+            None => (None, None, None),
+        };
+        if onchain.is_nft {
+            if json.attributes.is_none() {
+                warnings.push(String::from("missing attributes"));
+            }
+            if files.is_none() {
+                warnings.push(String::from("missing properties.files"));
+            }
+            if creators.is_none() {
+                warnings.push(String::from("missing properties.creators"));
+            }
+        }
+
+        let files = files
+            .unwrap_or_default()
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, entry)| OffchainFile::parse(idx, entry, &mut warnings))
+            .collect();
+        let creators = creators
+            .unwrap_or_default()
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, entry)| OffchainCreator::parse(idx, entry, &mut warnings))
+            .collect();
+
+        OffchainMetadata {
+            uri,
+            name: json.name,
+            symbol: json.symbol,
+            description: json.description,
+            image: json.image,
+            animation_url: json.animation_url,
+            external_url: json.external_url,
+            category,
+            attributes: json.attributes.unwrap_or_default(),
+            files,
+            creators,
+            warnings,
+        }
+    }
+}
+
+impl Output for OffchainMetadata {
+    fn struct_name(&self) -> String {
+        String::from("OffchainMetadata")
+    }
+
+    fn to_raw_struct(&self) -> String {
+        format!("{:#?}", self)
+    }
+
+    fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
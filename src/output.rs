@@ -1,35 +1,29 @@
 use std::error::Error;
-use std::fmt::Debug;
 
+// variants read as "output as ..."
+#[allow(clippy::enum_variant_names)]
 #[derive(Clone, Debug)]
 pub enum OutputFormat {
     AsStruct,
     AsJson,
+    /// newline delimited JSON, one compact JSON object per page printed as soon as it is ready
+    AsNdJson,
 }
 
 impl clap::ValueEnum for OutputFormat {
     fn value_variants<'a>() -> &'a [Self] {
-        &[Self::AsStruct, Self::AsJson]
+        &[Self::AsStruct, Self::AsJson, Self::AsNdJson]
     }
 
     fn to_possible_value(&self) -> Option<clap::builder::PossibleValue> {
         match self {
             Self::AsStruct => Some(clap::builder::PossibleValue::new("raw")),
             Self::AsJson => Some(clap::builder::PossibleValue::new("json")),
+            Self::AsNdJson => Some(clap::builder::PossibleValue::new("ndjson")),
         }
     }
 }
 
-pub fn print_struct<T: Debug>(data_struct: T) {
-    let type_name = std::any::type_name::<T>().split("::");
-    let size = type_name.clone().count();
-    let mut type_prefix = type_name.take(size - 1).collect::<Vec<&str>>().join("::");
-    if type_prefix.starts_with('&') {
-        type_prefix = type_prefix.strip_prefix('&').unwrap().to_string();
-    }
-    println!("{}::{:#?}", type_prefix, data_struct);
-}
-
 pub fn print_warning(msg: &str) {
     println!("{}", msg);
 }
@@ -50,4 +44,10 @@ pub trait Output {
 
     /// Serde serialize must be implemented, it is used to serialize structs to JSON
     fn to_json(&self) -> String;
+
+    /// struct can appear on a page several times, JSON pages always keep it in an array
+    /// so the shape of the page doesn't depend on how many there are
+    fn is_repeatable(&self) -> bool {
+        false
+    }
 }
@@ -1,3 +1,7 @@
+use std::io::{self, Write};
+
+use serde_json::{Map, Value};
+
 use crate::output::{Output, OutputFormat};
 
 pub struct Page {
@@ -17,21 +21,101 @@ impl Page {
         self.content.push(Box::new(item));
     }
 
+    /// move content of the other page to the end of this one
+    pub fn extend(&mut self, other: Page) {
+        self.content.extend(other.content);
+    }
+
     pub fn display(&self) {
         match self.format {
-            OutputFormat::AsStruct => {
-                for box_s in &self.content {
-                    let output = box_s.to_raw_struct();
-                    println!("se::{output}");
-                }
+            OutputFormat::AsStruct => self.display_raw(),
+            OutputFormat::AsJson => {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&self.to_json_value()).unwrap()
+                );
+            }
+            OutputFormat::AsNdJson => println!("{}", self.to_json_value()),
+        }
+    }
+
+    fn display_raw(&self) {
+        for box_s in &self.content {
+            let output = box_s.to_raw_struct();
+            println!("se::{output}");
+        }
+    }
+
+    /// page is a top level JSON object with `"<struct name>": <struct JSON>` elements,
+    /// repeatable structs are collected into an array in the order of appearance
+    fn to_json_value(&self) -> Value {
+        let mut groups: Vec<(String, bool, Vec<Value>)> = Vec::new();
+        for box_s in &self.content {
+            let name = box_s.struct_name();
+            let value: Value = serde_json::from_str(&box_s.to_json()).unwrap();
+            match groups
+                .iter_mut()
+                .find(|(group_name, _, _)| *group_name == name)
+            {
+                Some((_, _, items)) => items.push(value),
+                None => groups.push((name, box_s.is_repeatable(), vec![value])),
             }
+        }
+        let object = groups
+            .into_iter()
+            .map(|(name, is_repeatable, mut items)| {
+                let value = if items.len() == 1 && !is_repeatable {
+                    items.remove(0)
+                } else {
+                    Value::Array(items)
+                };
+                (name, value)
+            })
+            .collect::<Map<String, Value>>();
+        Value::Object(object)
+    }
+}
+
+/// Pages of a list command displayed as soon as they are pushed,
+/// in JSON format pages are elements of a single JSON array printed element by element
+pub struct PageStream {
+    format: OutputFormat,
+    pushed: usize,
+}
+
+impl PageStream {
+    pub fn new(format: OutputFormat) -> Self {
+        PageStream { format, pushed: 0 }
+    }
+
+    pub fn format(&self) -> &OutputFormat {
+        &self.format
+    }
+
+    /// new empty page of the stream format
+    pub fn page(&self) -> Page {
+        Page::new(self.format.clone())
+    }
+
+    pub fn push(&mut self, page: Page) {
+        match self.format {
             OutputFormat::AsJson => {
-                for box_s in &self.content {
-                    // TODO: maybe page will be top level JSON "object" {}
-                    // and all elements will be "<name call value>": <to_json call value>
-                    // it must produce valid JSON so it can be copied or piped to other utils that works with JSON like `jq`
-                    println!("{}", box_s.to_json());
-                }
+                let element = serde_json::to_string_pretty(&page.to_json_value()).unwrap();
+                let separator = if self.pushed == 0 { "[" } else { "," };
+                // indented as an element of a pretty printed array
+                print!("{separator}\n  {}", element.replace('\n', "\n  "));
+                let _ = io::stdout().flush();
+            }
+            _ => page.display(),
+        }
+        self.pushed += 1;
+    }
+
+    pub fn finish(self) {
+        if let OutputFormat::AsJson = self.format {
+            match self.pushed {
+                0 => println!("[]"),
+                _ => println!("\n]"),
             }
         }
     }
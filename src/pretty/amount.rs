@@ -0,0 +1,50 @@
+use serde::{Serialize, Serializer};
+use std::fmt;
+
+/// SOL amounts are lamports with 9 decimals
+pub const SOL_DECIMALS: u8 = 9;
+
+/// Integer amount of base units (lamports, token base units) displayed as an exact decimal
+/// with `decimals` applied, serialized as a decimal string, floating point numbers can't
+/// hold large token amounts exactly
+#[derive(Clone, Copy)]
+pub struct PrettyAmount {
+    value: i128,
+    decimals: u8,
+}
+
+impl PrettyAmount {
+    pub fn new(value: i128, decimals: u8) -> Self {
+        PrettyAmount { value, decimals }
+    }
+}
+
+impl fmt::Display for PrettyAmount {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sign = if self.value < 0 { "-" } else { "" };
+        let abs = self.value.unsigned_abs();
+        let unit = 10u128.pow(self.decimals as u32);
+        let fraction = format!("{:0width$}", abs % unit, width = self.decimals as usize);
+        let fraction = fraction.trim_end_matches('0');
+        if fraction.is_empty() {
+            write!(f, "{}{}", sign, abs / unit)
+        } else {
+            write!(f, "{}{}.{}", sign, abs / unit, fraction)
+        }
+    }
+}
+
+impl fmt::Debug for PrettyAmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl Serialize for PrettyAmount {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
@@ -1,2 +1,6 @@
+pub mod amount;
 pub mod string;
+pub mod table;
 pub mod public_key;
+pub mod time;
+pub mod uri;
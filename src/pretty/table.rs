@@ -0,0 +1,39 @@
+/// Rows aligned into columns separated with two spaces, the first row is a header
+pub fn format_table(rows: &[Vec<String>]) -> String {
+    let mut widths: Vec<usize> = Vec::new();
+    for row in rows {
+        for (idx, cell) in row.iter().enumerate() {
+            match widths.get_mut(idx) {
+                Some(width) => *width = (*width).max(cell.chars().count()),
+                None => widths.push(cell.chars().count()),
+            }
+        }
+    }
+    rows.iter()
+        .map(|row| {
+            let line = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{:width$}", cell))
+                .collect::<Vec<String>>()
+                .join("  ");
+            line.trim_end().to_string()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// CSV line, cells with commas, quotes or line breaks are quoted
+pub fn csv_line(cells: &[String]) -> String {
+    cells
+        .iter()
+        .map(|cell| {
+            if cell.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", cell.replace('"', "\"\""))
+            } else {
+                cell.clone()
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(",")
+}
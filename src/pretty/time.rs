@@ -0,0 +1,61 @@
+use chrono::{DateTime, NaiveDate, SecondsFormat};
+use serde::{Serialize, Serializer};
+use std::fmt;
+
+/// Unix timestamp displayed as UTC date and time, serialized as is
+#[derive(Clone, Copy)]
+pub struct PrettyTimestamp {
+    value: i64,
+}
+
+impl From<i64> for PrettyTimestamp {
+    fn from(value: i64) -> Self {
+        PrettyTimestamp { value }
+    }
+}
+
+impl PrettyTimestamp {
+    /// RFC 3339 UTC time for machine readable output like CSV, the timestamp if it is out of range
+    pub fn to_rfc3339(self) -> String {
+        match DateTime::from_timestamp(self.value, 0) {
+            Some(date_time) => date_time.to_rfc3339_opts(SecondsFormat::Secs, true),
+            None => self.value.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for PrettyTimestamp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match DateTime::from_timestamp(self.value, 0) {
+            Some(date_time) => write!(f, "{}", date_time.format("%Y-%m-%d %H:%M:%S UTC")),
+            None => write!(f, "{}", self.value),
+        }
+    }
+}
+
+impl fmt::Debug for PrettyTimestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self, self.value)
+    }
+}
+
+impl Serialize for PrettyTimestamp {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_i64(self.value)
+    }
+}
+
+/// Parses unix timestamp, RFC 3339 date and time or a date (midnight UTC)
+pub fn parse_timestamp(value: &str) -> Option<i64> {
+    if let Ok(timestamp) = value.parse::<i64>() {
+        return Some(timestamp);
+    }
+    if let Ok(date_time) = DateTime::parse_from_rfc3339(value) {
+        return Some(date_time.timestamp());
+    }
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()?;
+    Some(date.and_hms_opt(0, 0, 0)?.and_utc().timestamp())
+}
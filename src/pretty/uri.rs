@@ -0,0 +1,182 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{env, fmt, sync::OnceLock};
+
+const DEFAULT_IPFS_GATEWAY: &str = "https://ipfs.io";
+const DEFAULT_ARWEAVE_GATEWAY: &str = "https://arweave.net";
+
+static IPFS_GATEWAY_OVERRIDE: OnceLock<String> = OnceLock::new();
+static ARWEAVE_GATEWAY_OVERRIDE: OnceLock<String> = OnceLock::new();
+
+/// Override IPFS gateway for the whole CLI run, takes precedence over `SE_IPFS_GATEWAY` env var
+pub fn set_ipfs_gateway(gateway: String) {
+    IPFS_GATEWAY_OVERRIDE.get_or_init(|| gateway);
+}
+
+/// Override Arweave gateway for the whole CLI run, takes precedence over `SE_ARWEAVE_GATEWAY` env var
+pub fn set_arweave_gateway(gateway: String) {
+    ARWEAVE_GATEWAY_OVERRIDE.get_or_init(|| gateway);
+}
+
+fn ipfs_gateway() -> String {
+    IPFS_GATEWAY_OVERRIDE
+        .get()
+        .cloned()
+        .or_else(|| env::var("SE_IPFS_GATEWAY").ok())
+        .unwrap_or(DEFAULT_IPFS_GATEWAY.to_string())
+}
+
+fn arweave_gateway() -> String {
+    ARWEAVE_GATEWAY_OVERRIDE
+        .get()
+        .cloned()
+        .or_else(|| env::var("SE_ARWEAVE_GATEWAY").ok())
+        .unwrap_or(DEFAULT_ARWEAVE_GATEWAY.to_string())
+}
+
+/// Content addressed storage a URI points to, holds content id with optional path and query
+enum ContentAddress {
+    Ipfs(String),
+    Arweave(String),
+}
+
+impl ContentAddress {
+    /// canonical content address independent of a gateway, e.g. ipfs://<CID>/1.json
+    fn canonical(&self) -> String {
+        match self {
+            ContentAddress::Ipfs(cid_path) => format!("ipfs://{}", cid_path),
+            ContentAddress::Arweave(id_path) => format!("ar://{}", id_path),
+        }
+    }
+
+    fn gateway_url(&self) -> String {
+        match self {
+            ContentAddress::Ipfs(cid_path) => {
+                format!("{}/ipfs/{}", ipfs_gateway().trim_end_matches('/'), cid_path)
+            }
+            ContentAddress::Arweave(id_path) => {
+                format!("{}/{}", arweave_gateway().trim_end_matches('/'), id_path)
+            }
+        }
+    }
+}
+
+fn is_raw_cid(s: &str) -> bool {
+    let cid = s.split(['/', '?']).next().unwrap_or_default();
+    let is_v0 = cid.len() == 46 && cid.starts_with("Qm");
+    let is_v1 = cid.len() > 50 && cid.starts_with("baf");
+    (is_v0 || is_v1) && cid.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+fn is_arweave_host(host: &str) -> bool {
+    host == "arweave.net" || host.ends_with(".arweave.net") || host == "ar-io.net"
+}
+
+/// Finds content address in `ipfs://`, `ar://`, raw CIDs and IPFS or Arweave gateway URLs
+fn parse_content_address(uri: &str) -> Option<ContentAddress> {
+    if let Some(cid_path) = uri.strip_prefix("ipfs://") {
+        let cid_path = cid_path.strip_prefix("ipfs/").unwrap_or(cid_path);
+        return Some(ContentAddress::Ipfs(cid_path.to_string()));
+    }
+    if let Some(id_path) = uri.strip_prefix("ar://") {
+        return Some(ContentAddress::Arweave(id_path.to_string()));
+    }
+    if is_raw_cid(uri) {
+        return Some(ContentAddress::Ipfs(uri.to_string()));
+    }
+
+    let without_scheme = uri
+        .strip_prefix("https://")
+        .or_else(|| uri.strip_prefix("http://"))?;
+    let (host, path) = without_scheme
+        .split_once('/')
+        .unwrap_or((without_scheme, ""));
+    // subdomain gateway, e.g. https://<CID>.ipfs.w3s.link/1.json
+    if let Some((cid, _)) = host.split_once(".ipfs.") {
+        if is_raw_cid(cid) {
+            let cid_path = if path.is_empty() {
+                cid.to_string()
+            } else {
+                format!("{}/{}", cid, path)
+            };
+            return Some(ContentAddress::Ipfs(cid_path));
+        }
+    }
+    // path gateway, e.g. https://ipfs.io/ipfs/<CID>/1.json
+    if let Some(cid_path) = path.strip_prefix("ipfs/") {
+        if is_raw_cid(cid_path) {
+            return Some(ContentAddress::Ipfs(cid_path.to_string()));
+        }
+    }
+    if is_arweave_host(host) && !path.is_empty() {
+        return Some(ContentAddress::Arweave(path.to_string()));
+    }
+    None
+}
+
+/// URI that knows about content addressed storages (IPFS, Arweave),
+/// resolves content through the preferred gateway and displays canonical content address.
+/// Serialized as is, the gateway URL depends on the settings of the CLI run
+#[derive(Clone, Default)]
+pub struct PrettyUri {
+    value: String,
+}
+
+impl PrettyUri {
+    pub fn is_empty(&self) -> bool {
+        self.value.is_empty()
+    }
+
+    /// canonical content address (ipfs:// or ar://) if the URI points to content addressed storage
+    pub fn content_address(&self) -> Option<String> {
+        parse_content_address(&self.value).map(|address| address.canonical())
+    }
+
+    /// URL to fetch the content from, IPFS and Arweave content is fetched through preferred gateways
+    pub fn resolve(&self) -> String {
+        match parse_content_address(&self.value) {
+            Some(address) => address.gateway_url(),
+            None => self.value.clone(),
+        }
+    }
+}
+
+impl From<String> for PrettyUri {
+    fn from(s: String) -> Self {
+        Self {
+            value: s.trim_end_matches('\0').trim().into(),
+        }
+    }
+}
+
+impl fmt::Display for PrettyUri {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.resolve())
+    }
+}
+
+impl fmt::Debug for PrettyUri {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.content_address() {
+            Some(address) => write!(f, "[{}] {:?}", address, self.resolve()),
+            None => fmt::Debug::fmt(&self.value, f),
+        }
+    }
+}
+
+impl Serialize for PrettyUri {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.value.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for PrettyUri {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        String::deserialize(deserializer).map(PrettyUri::from)
+    }
+}
@@ -1,10 +1,211 @@
-use solana_client::rpc_client::RpcClient;
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind},
+    rpc_client::RpcClient,
+    rpc_request::{RpcError, RpcRequest, RpcResponseErrorData},
+};
 use solana_sdk::commitment_config::CommitmentConfig;
-use std::env;
+use std::{
+    collections::BTreeMap,
+    env,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Mutex, OnceLock,
+    },
+    thread,
+    time::{Duration, Instant},
+};
 
-pub fn init_connection() -> RpcClient {
-    // TODO: memoize created client
-    let rpc_url =
-        env::var("SE_RPC_URL").unwrap_or("http://api.mainnet-beta.solana.com".to_string());
-    RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed())
+const DEFAULT_RATE_LIMIT: u32 = 40;
+/// retries of a request rejected with 429 Too Many Requests, the pause doubles with each one
+const MAX_RATE_LIMITED_RETRIES: u32 = 5;
+const RATE_LIMITED_PAUSE: Duration = Duration::from_millis(500);
+
+static RPC_CLIENT: OnceLock<RpcClient> = OnceLock::new();
+static RATE_LIMIT_OVERRIDE: OnceLock<u32> = OnceLock::new();
+static RATE_LIMITER: OnceLock<RateLimiter> = OnceLock::new();
+
+pub fn rpc_url() -> String {
+    env::var("SE_RPC_URL").unwrap_or("http://api.mainnet-beta.solana.com".to_string())
+}
+
+/// RPC client shared by the whole CLI run
+pub fn init_connection() -> &'static RpcClient {
+    RPC_CLIENT
+        .get_or_init(|| RpcClient::new_with_commitment(rpc_url(), CommitmentConfig::confirmed()))
+}
+
+/// Override max RPC requests per second of rate limited requests,
+/// takes precedence over `SE_RPC_RATE_LIMIT` env var
+pub fn set_rate_limit(requests_per_second: u32) {
+    RATE_LIMIT_OVERRIDE.get_or_init(|| requests_per_second);
+}
+
+fn rate_limit() -> u32 {
+    RATE_LIMIT_OVERRIDE
+        .get()
+        .copied()
+        .or_else(|| env::var("SE_RPC_RATE_LIMIT").ok()?.parse().ok())
+        .unwrap_or(DEFAULT_RATE_LIMIT)
+}
+
+/// Token bucket of RPC requests, refilled with `rate` tokens per second up to one second burst
+struct RateLimiter {
+    rate: f64,
+    bucket: Mutex<Bucket>,
+}
+
+struct Bucket {
+    tokens: f64,
+    /// time the tokens were counted at, in the future while requests are paused
+    updated_at: Instant,
+}
+
+impl RateLimiter {
+    fn new(requests_per_second: u32) -> Self {
+        let rate = requests_per_second.max(1) as f64;
+        RateLimiter {
+            rate,
+            bucket: Mutex::new(Bucket {
+                tokens: rate,
+                updated_at: Instant::now(),
+            }),
+        }
+    }
+
+    /// wait until a request can be sent
+    fn acquire(&self) {
+        loop {
+            let wait = {
+                let mut bucket = self.bucket.lock().unwrap();
+                let now = Instant::now();
+                if now > bucket.updated_at {
+                    let elapsed = (now - bucket.updated_at).as_secs_f64();
+                    bucket.tokens = (bucket.tokens + elapsed * self.rate).min(self.rate);
+                    bucket.updated_at = now;
+                }
+                if bucket.tokens >= 1.0 {
+                    bucket.tokens -= 1.0;
+                    return;
+                }
+                bucket.updated_at.saturating_duration_since(now)
+                    + Duration::from_secs_f64((1.0 - bucket.tokens) / self.rate)
+            };
+            thread::sleep(wait);
+        }
+    }
+
+    /// stop all requests for a while after the RPC rejected one with 429
+    fn pause(&self, pause: Duration) {
+        let mut bucket = self.bucket.lock().unwrap();
+        bucket.tokens = 0.0;
+        bucket.updated_at = bucket.updated_at.max(Instant::now() + pause);
+    }
+}
+
+fn is_rate_limited(err: &ClientError) -> bool {
+    match err.kind() {
+        ClientErrorKind::Reqwest(err) => err.status() == Some(StatusCode::TOO_MANY_REQUESTS),
+        // some providers reply with 429 in JSON RPC error
+        ClientErrorKind::RpcError(RpcError::RpcResponseError { code, .. }) => *code == 429,
+        _ => false,
+    }
+}
+
+/// Send RPC request through the shared rate limiter, requests rejected with 429 Too Many Requests
+/// pause all requests and are retried
+pub fn send_rate_limited<T>(
+    request: impl Fn(&RpcClient) -> Result<T, ClientError>,
+) -> Result<T, ClientError> {
+    let limiter = RATE_LIMITER.get_or_init(|| RateLimiter::new(rate_limit()));
+    let mut retries = 0;
+    loop {
+        limiter.acquire();
+        match request(init_connection()) {
+            Err(err) if is_rate_limited(&err) && retries < MAX_RATE_LIMITED_RETRIES => {
+                limiter.pause(RATE_LIMITED_PAUSE * 2u32.pow(retries));
+                retries += 1;
+            }
+            res => return res,
+        }
+    }
+}
+
+/// Fetch data for the items with at most `concurrency` requests in flight and pass results
+/// to `on_result` in the order of items as soon as they are ready,
+/// fetching stops when `on_result` returns false
+pub fn fetch_concurrently<T: Sync, R: Send>(
+    items: &[T],
+    concurrency: usize,
+    fetch: impl Fn(&T) -> R + Sync,
+    mut on_result: impl FnMut(usize, R) -> bool,
+) {
+    let workers = concurrency.clamp(1, items.len().max(1));
+    if workers == 1 {
+        for (idx, item) in items.iter().enumerate() {
+            if !on_result(idx, fetch(item)) {
+                return;
+            }
+        }
+        return;
+    }
+    let next = AtomicUsize::new(0);
+    thread::scope(|scope| {
+        let (sender, receiver) = mpsc::channel();
+        for _ in 0..workers {
+            let sender = sender.clone();
+            let (next, fetch) = (&next, &fetch);
+            scope.spawn(move || loop {
+                let idx = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(idx) else {
+                    break;
+                };
+                // receiver is gone when results are not needed anymore
+                if sender.send((idx, fetch(item))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        // results of items fetched ahead of the next one in order
+        let mut ready = BTreeMap::new();
+        let mut expected = 0;
+        for (idx, result) in receiver {
+            ready.insert(idx, result);
+            while let Some(result) = ready.remove(&expected) {
+                if !on_result(expected, result) {
+                    next.store(items.len(), Ordering::Relaxed);
+                    return;
+                }
+                expected += 1;
+            }
+        }
+    });
+}
+
+/// Send JSON RPC request with named params (JSON object),
+/// RpcClient::send supports only positional params but RPC extensions like DAS API expect named ones
+pub fn send_with_named_params<T: DeserializeOwned>(
+    method: &'static str,
+    params: Value,
+) -> Result<T, ClientError> {
+    let request = RpcRequest::Custom { method };
+    let mut res: Value = reqwest::blocking::Client::new()
+        .post(rpc_url())
+        .json(&request.build_request_json(1, params))
+        .send()?
+        .error_for_status()?
+        .json()?;
+    if res["error"].is_object() {
+        return Err(RpcError::RpcResponseError {
+            code: res["error"]["code"].as_i64().unwrap_or_default(),
+            message: res["error"]["message"].as_str().unwrap_or_default().to_string(),
+            data: RpcResponseErrorData::Empty,
+        }
+        .into());
+    }
+    Ok(serde_json::from_value(res["result"].take())?)
 }
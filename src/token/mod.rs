@@ -1,4 +1,7 @@
-use crate::{output::Output, pretty::string::PrettyString};
+use crate::{
+    output::Output,
+    pretty::{string::PrettyString, uri::PrettyUri},
+};
 use mpl_token_metadata::{
     accounts::Metadata,
     types::{Collection, CollectionDetails, Creator, Key, ProgrammableConfig, TokenStandard, Uses},
@@ -11,7 +14,7 @@ use spl_token::state::{Account as SolanaSplTokenAccount, AccountState, Mint};
 /// Wallet/Owner Account -> Token Account -> Mint Account -> Metadata (PDA) Account
 ///
 /// Wallet/Owner Account - owner, usually a someones wallet, System program owns and operates owner accounts
-
+///
 /// Mint Account - stores information about the token itself, its suplly, authorities etc
 #[derive(Debug, Serialize)]
 pub struct TokenMint {
@@ -64,7 +67,7 @@ pub struct TokenMetadata {
     pub mint: Pubkey,
     pub name: PrettyString,
     pub symbol: PrettyString,
-    pub uri: PrettyString,
+    pub uri: PrettyUri,
     pub seller_fee_basis_points: u16,
     pub creators: Option<Vec<Creator>>,
     pub primary_sale_happened: bool,
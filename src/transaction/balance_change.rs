@@ -0,0 +1,188 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+use solana_transaction_status::{
+    option_serializer::OptionSerializer, UiTransactionStatusMeta, UiTransactionTokenBalance,
+};
+
+use crate::{
+    output::Output,
+    pretty::{
+        amount::{PrettyAmount, SOL_DECIMALS},
+        public_key::PrettyPublicKey,
+    },
+};
+
+/// Name and symbol of a token mint from its Token Metadata account
+/// or Token-2022 metadata extension
+#[derive(Clone, Debug)]
+pub struct TokenName {
+    pub name: String,
+    pub symbol: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SolBalanceChange {
+    pub account: PrettyPublicKey,
+    pub pre: PrettyAmount,
+    pub post: PrettyAmount,
+    pub change: PrettyAmount,
+}
+
+/// Token balance change of an owner summed over all its token accounts of the mint,
+/// amounts are in tokens with mint decimals applied
+#[derive(Debug, Serialize)]
+pub struct TokenBalanceChange {
+    pub owner: Option<PrettyPublicKey>,
+    pub mint: PrettyPublicKey,
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub decimals: u8,
+    pub pre: PrettyAmount,
+    pub post: PrettyAmount,
+    pub change: PrettyAmount,
+}
+
+/// SOL and token balance changes made by a transaction,
+/// the fee is shown separately and excluded from the fee payer SOL change
+#[derive(Debug, Serialize)]
+pub struct BalanceChanges {
+    pub fee_payer: PrettyPublicKey,
+    pub fee: PrettyAmount,
+    pub sol: Vec<SolBalanceChange>,
+    pub tokens: Vec<TokenBalanceChange>,
+}
+
+fn lamports_to_sol(lamports: i128) -> PrettyAmount {
+    PrettyAmount::new(lamports, SOL_DECIMALS)
+}
+
+/// mints of all token balances of the transaction
+pub fn token_mints(meta: &UiTransactionStatusMeta) -> Vec<Pubkey> {
+    let mut mints: Vec<Pubkey> = token_balances(&meta.pre_token_balances)
+        .iter()
+        .chain(token_balances(&meta.post_token_balances).iter())
+        .filter_map(|balance| balance.mint.parse().ok())
+        .collect();
+    mints.sort();
+    mints.dedup();
+    mints
+}
+
+pub fn token_balances(
+    balances: &OptionSerializer<Vec<UiTransactionTokenBalance>>,
+) -> &[UiTransactionTokenBalance] {
+    match balances {
+        OptionSerializer::Some(balances) => balances,
+        _ => &[],
+    }
+}
+
+impl BalanceChanges {
+    pub fn new(
+        meta: &UiTransactionStatusMeta,
+        account_keys: &[Pubkey],
+        token_names: &HashMap<Pubkey, TokenName>,
+    ) -> Self {
+        let fee_payer = account_keys.first().copied().unwrap_or_default();
+        let sol = account_keys
+            .iter()
+            .zip(meta.pre_balances.iter().zip(meta.post_balances.iter()))
+            .enumerate()
+            .filter_map(|(idx, (pubkey, (pre, post)))| {
+                let mut change = *post as i128 - *pre as i128;
+                if idx == 0 {
+                    change += meta.fee as i128;
+                }
+                (change != 0).then(|| SolBalanceChange {
+                    account: (*pubkey).into(),
+                    pre: lamports_to_sol(*pre as i128),
+                    post: lamports_to_sol(*post as i128),
+                    change: lamports_to_sol(change),
+                })
+            })
+            .collect();
+
+        BalanceChanges {
+            fee_payer: fee_payer.into(),
+            fee: lamports_to_sol(meta.fee as i128),
+            sol,
+            tokens: token_balance_changes(meta, token_names),
+        }
+    }
+}
+
+/// token balance of an owner for a mint, keyed by (owner, mint)
+type OwnerMintBalance = ((Option<String>, String), (u8, i128, i128));
+
+fn token_balance_changes(
+    meta: &UiTransactionStatusMeta,
+    token_names: &HashMap<Pubkey, TokenName>,
+) -> Vec<TokenBalanceChange> {
+    // (owner, mint) -> (decimals, pre amount, post amount), in order of appearance
+    let mut changes: Vec<OwnerMintBalance> = Vec::new();
+    let mut add = |balance: &UiTransactionTokenBalance, is_post: bool| {
+        let owner = Option::<String>::from(balance.owner.clone());
+        let key = (owner, balance.mint.clone());
+        let amount = balance
+            .ui_token_amount
+            .amount
+            .parse::<i128>()
+            .unwrap_or_default();
+        let idx = match changes.iter().position(|(k, _)| *k == key) {
+            Some(idx) => idx,
+            None => {
+                changes.push((key, (balance.ui_token_amount.decimals, 0, 0)));
+                changes.len() - 1
+            }
+        };
+        let (_, (_, pre, post)) = &mut changes[idx];
+        if is_post {
+            *post += amount;
+        } else {
+            *pre += amount;
+        }
+    };
+    for balance in token_balances(&meta.pre_token_balances) {
+        add(balance, false);
+    }
+    for balance in token_balances(&meta.post_token_balances) {
+        add(balance, true);
+    }
+
+    changes
+        .into_iter()
+        .filter(|(_, (_, pre, post))| pre != post)
+        .map(|((owner, mint), (decimals, pre, post))| {
+            let mint = mint.parse::<Pubkey>().unwrap_or_default();
+            let token_name = token_names.get(&mint);
+            TokenBalanceChange {
+                owner: owner
+                    .and_then(|owner| owner.parse::<Pubkey>().ok())
+                    .map(PrettyPublicKey::from),
+                mint: mint.into(),
+                name: token_name.map(|token| token.name.clone()),
+                symbol: token_name.map(|token| token.symbol.clone()),
+                decimals,
+                pre: PrettyAmount::new(pre, decimals),
+                post: PrettyAmount::new(post, decimals),
+                change: PrettyAmount::new(post - pre, decimals),
+            }
+        })
+        .collect()
+}
+
+impl Output for BalanceChanges {
+    fn struct_name(&self) -> String {
+        String::from("BalanceChanges")
+    }
+
+    fn to_raw_struct(&self) -> String {
+        format!("{:#?}", self)
+    }
+
+    fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
@@ -0,0 +1,352 @@
+use std::{process::exit, str::FromStr};
+
+use serde::Serialize;
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind},
+    rpc_response::RpcConfirmedTransactionStatusWithSignature,
+};
+use solana_sdk::{program_pack::Pack, pubkey::Pubkey, signature::Signature};
+use solana_transaction_status::UiTransactionTokenBalance;
+
+use super::{
+    balance_change::token_balances,
+    instruction::{ASSOCIATED_TOKEN_ID, TOKEN_2022_ID},
+    reader::{get_tx_data, SignaturePages, TransactionFetchError, TransactionsPagination},
+    TransactionData,
+};
+use crate::{
+    output::{print_error, print_warning, Output, OutputFormat},
+    page::PageStream,
+    pretty::{
+        amount::{PrettyAmount, SOL_DECIMALS},
+        public_key::PrettyPublicKey,
+        table::{csv_line, format_table},
+        time::PrettyTimestamp,
+    },
+    rpc,
+};
+
+/// Balance change made by a transaction, `balance` is the balance right after it
+#[derive(Debug, Serialize)]
+pub struct BalanceHistoryEntry {
+    pub slot: u64,
+    pub block_time: Option<PrettyTimestamp>,
+    pub signature: String,
+    pub delta: PrettyAmount,
+    pub balance: PrettyAmount,
+}
+
+impl BalanceHistoryEntry {
+    fn to_row(&self, time: impl Fn(PrettyTimestamp) -> String) -> Vec<String> {
+        vec![
+            self.slot.to_string(),
+            self.block_time.map(time).unwrap_or_default(),
+            self.signature.clone(),
+            self.delta.to_string(),
+            self.balance.to_string(),
+        ]
+    }
+}
+
+impl Output for BalanceHistoryEntry {
+    fn struct_name(&self) -> String {
+        String::from("BalanceHistoryEntry")
+    }
+
+    fn to_raw_struct(&self) -> String {
+        format!("{:#?}", self)
+    }
+
+    fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+/// SOL balance of an address or its token balance of a mint, newest change first,
+/// one table row per change in raw output
+#[derive(Debug, Serialize)]
+pub struct BalanceHistory {
+    pub address: PrettyPublicKey,
+    pub mint: Option<PrettyPublicKey>,
+    /// associated token account of the address the token balance is read from
+    pub token_account: Option<PrettyPublicKey>,
+    pub entries: Vec<BalanceHistoryEntry>,
+}
+
+impl Output for BalanceHistory {
+    fn struct_name(&self) -> String {
+        String::from("BalanceHistory")
+    }
+
+    fn to_raw_struct(&self) -> String {
+        let balance = match (&self.mint, &self.token_account) {
+            (Some(mint), Some(token_account)) => {
+                format!("mint {:?} in token account {:?}", mint, token_account)
+            }
+            _ => String::from("SOL"),
+        };
+        let rows: Vec<Vec<String>> = std::iter::once(
+            ["SLOT", "BLOCK TIME", "SIGNATURE", "DELTA", "BALANCE"]
+                .map(String::from)
+                .to_vec(),
+        )
+        .chain(
+            self.entries
+                .iter()
+                .map(|entry| entry.to_row(|time| time.to_string())),
+        )
+        .collect();
+        format!(
+            "{} of {:?}, {}\n{}",
+            self.struct_name(),
+            self.address,
+            balance,
+            format_table(&rows)
+        )
+    }
+
+    fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+/// Account the balance is read from, the address itself for SOL or its associated token account,
+/// other token accounts of the address are not tracked
+struct TrackedAccount {
+    pubkey: Pubkey,
+    mint: Option<Pubkey>,
+    decimals: u8,
+}
+
+impl TrackedAccount {
+    fn new(address: &Pubkey, mint: Option<Pubkey>) -> Result<Self, ClientError> {
+        let Some(mint) = mint else {
+            return Ok(TrackedAccount {
+                pubkey: *address,
+                mint: None,
+                decimals: SOL_DECIMALS,
+            });
+        };
+        let mint_account = rpc::send_rate_limited(|rpc_con| rpc_con.get_account(&mint))?;
+        let token_program = mint_account.owner;
+        // Token-2022 mints keep extensions after the base mint state
+        let mint_state = match token_program {
+            spl_token::ID | TOKEN_2022_ID => mint_account
+                .data
+                .get(..spl_token::state::Mint::LEN)
+                .and_then(|data| spl_token::state::Mint::unpack(data).ok()),
+            _ => None,
+        };
+        let Some(mint_state) = mint_state else {
+            return Err(ClientError::from(ClientErrorKind::Custom(format!(
+                "{} is not a token mint",
+                mint
+            ))));
+        };
+        let (token_account, _) = Pubkey::find_program_address(
+            &[address.as_ref(), token_program.as_ref(), mint.as_ref()],
+            &ASSOCIATED_TOKEN_ID,
+        );
+        Ok(TrackedAccount {
+            pubkey: token_account,
+            mint: Some(mint),
+            decimals: mint_state.decimals,
+        })
+    }
+
+    /// balance change of the account made by the transaction, `None` when there is none
+    fn entry(
+        &self,
+        data: &TransactionData,
+        status: &RpcConfirmedTransactionStatusWithSignature,
+    ) -> Option<BalanceHistoryEntry> {
+        let meta = data.meta.as_ref()?;
+        let idx = data
+            .account_keys()
+            .iter()
+            .position(|key| *key == self.pubkey)?;
+        let (pre, post) = match self.mint {
+            None => (
+                *meta.pre_balances.get(idx)? as i128,
+                *meta.post_balances.get(idx)? as i128,
+            ),
+            // token account has no token balance before it is created and after it is closed
+            Some(_) => {
+                let token_amount = |balances: &[UiTransactionTokenBalance]| {
+                    balances
+                        .iter()
+                        .find(|balance| balance.account_index as usize == idx)
+                        .and_then(|balance| balance.ui_token_amount.amount.parse::<i128>().ok())
+                        .unwrap_or_default()
+                };
+                (
+                    token_amount(token_balances(&meta.pre_token_balances)),
+                    token_amount(token_balances(&meta.post_token_balances)),
+                )
+            }
+        };
+        (pre != post).then(|| BalanceHistoryEntry {
+            slot: status.slot,
+            block_time: status.block_time.map(PrettyTimestamp::from),
+            signature: status.signature.clone(),
+            delta: PrettyAmount::new(post - pre, self.decimals),
+            balance: PrettyAmount::new(post, self.decimals),
+        })
+    }
+}
+
+/// Balance history output, the table and JSON are displayed when the history is read,
+/// NDJSON and CSV rows as soon as they are fetched
+struct HistoryListing {
+    csv: bool,
+    stream: PageStream,
+    history: BalanceHistory,
+    failures: Vec<TransactionFetchError>,
+}
+
+impl HistoryListing {
+    fn new(history: BalanceHistory, csv: bool, output_format: OutputFormat) -> Self {
+        if csv {
+            println!("slot,time,signature,delta,balance");
+        }
+        HistoryListing {
+            csv,
+            stream: PageStream::new(output_format),
+            history,
+            failures: Vec::new(),
+        }
+    }
+
+    fn is_row_streamed(&self) -> bool {
+        matches!(self.stream.format(), OutputFormat::AsNdJson)
+    }
+
+    fn add(&mut self, entry: BalanceHistoryEntry) {
+        if self.csv {
+            println!("{}", csv_line(&entry.to_row(|time| time.to_rfc3339())));
+        } else if self.is_row_streamed() {
+            let mut page = self.stream.page();
+            page.add(entry);
+            self.stream.push(page);
+        } else {
+            self.history.entries.push(entry);
+        }
+    }
+
+    fn add_failure(&mut self, failure: TransactionFetchError) {
+        if self.csv {
+            // keep stdout a valid CSV
+            eprintln!(
+                "can't fetch transaction {}: {}",
+                failure.signature, failure.error
+            );
+        } else if self.is_row_streamed() {
+            let mut page = self.stream.page();
+            page.add(failure);
+            self.stream.push(page);
+        } else {
+            self.failures.push(failure);
+        }
+    }
+
+    fn finish(self) {
+        if self.csv {
+            return;
+        }
+        if self.is_row_streamed() {
+            self.stream.finish();
+            return;
+        }
+        let mut page = self.stream.page();
+        page.add(self.history);
+        self.failures
+            .into_iter()
+            .for_each(|failure| page.add(failure));
+        page.display();
+    }
+}
+
+/// Show how SOL balance of the address, or its token balance of the mint, changed
+/// from the newest transaction. Balances are read from pre and post balances of transaction meta,
+/// transactions that don't change the balance are skipped and don't count to the limit
+pub fn show_balance_history(
+    address: &str,
+    mint: Option<Pubkey>,
+    pagination: &TransactionsPagination,
+    concurrency: usize,
+    csv: bool,
+    output_format: OutputFormat,
+) {
+    let acc_pubkey = match Pubkey::from_str(address) {
+        Ok(pubkey) => pubkey,
+        Err(_) => {
+            print_warning(
+                format!("address {:?} is not a valid solana public key", address).as_str(),
+            );
+            exit(1);
+        }
+    };
+    if let Some(cursor) = pagination.invalid_cursor() {
+        print_warning(format!("signature {:?} is not a valid", cursor).as_str());
+        exit(1);
+    }
+    let tracked = match TrackedAccount::new(&acc_pubkey, mint) {
+        Ok(tracked) => tracked,
+        Err(err) => {
+            print_error(err);
+            exit(1);
+        }
+    };
+
+    let history = BalanceHistory {
+        address: acc_pubkey.into(),
+        mint: tracked.mint.map(PrettyPublicKey::from),
+        token_account: tracked.mint.map(|_| tracked.pubkey.into()),
+        entries: Vec::new(),
+    };
+    let mut listing = HistoryListing::new(history, csv, output_format);
+    let limit = pagination.max_count();
+    let mut signature_pages = SignaturePages::new(tracked.pubkey, pagination);
+    let mut listed = 0;
+    while listed < limit {
+        let statuses = match signature_pages.next(limit - listed) {
+            Ok(statuses) => statuses,
+            Err(err) => {
+                print_error(err);
+                exit(1);
+            }
+        };
+        if statuses.is_empty() {
+            break;
+        }
+        let signatures: Vec<&str> = statuses
+            .iter()
+            .map(|status| status.signature.as_str())
+            .collect();
+        rpc::fetch_concurrently(
+            &signatures,
+            concurrency,
+            |signature| {
+                let sig = Signature::from_str(signature)
+                    .map_err(|err| ClientError::from(ClientErrorKind::Custom(err.to_string())))?;
+                get_tx_data(&sig)
+            },
+            |idx, data| {
+                let status = &statuses[idx];
+                match data {
+                    Ok(data) => {
+                        if let Some(entry) = tracked.entry(&data, status) {
+                            listing.add(entry);
+                            listed += 1;
+                        }
+                    }
+                    Err(err) => {
+                        listing.add_failure(TransactionFetchError::new(&status.signature, err))
+                    }
+                }
+                listed < limit
+            },
+        );
+    }
+    listing.finish();
+}
@@ -0,0 +1,160 @@
+use std::{fs, path::Path, process::exit};
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use serde::Serialize;
+use solana_sdk::{
+    packet::PACKET_DATA_SIZE, signature::Signature, transaction::VersionedTransaction,
+};
+
+use super::{reader::resolve_lookup_tables, LookupSource, TransactionData};
+use crate::{
+    output::{print_error, print_warning, Output, OutputFormat},
+    page::Page,
+    pretty::public_key::PrettyPublicKey,
+};
+
+/// Deserializes a transaction from base64 or base58 encoded string, the string or raw
+/// transaction bytes can be stored in a file
+pub fn parse_transaction(input: &str) -> Option<VersionedTransaction> {
+    let input = input.trim();
+    if Path::new(input).is_file() {
+        let content = fs::read(input).ok()?;
+        return match std::str::from_utf8(&content) {
+            Ok(text) if !text.trim().is_empty() => parse_encoded_transaction(text.trim()),
+            _ => deserialize_transaction(&content),
+        };
+    }
+    parse_encoded_transaction(input)
+}
+
+fn parse_encoded_transaction(encoded: &str) -> Option<VersionedTransaction> {
+    // base58 alphabet is a subset of base64 one, try both encodings
+    BASE64
+        .decode(encoded)
+        .ok()
+        .and_then(|bytes| deserialize_transaction(&bytes))
+        .or_else(|| {
+            bs58::decode(encoded)
+                .into_vec()
+                .ok()
+                .and_then(|bytes| deserialize_transaction(&bytes))
+        })
+}
+
+fn deserialize_transaction(bytes: &[u8]) -> Option<VersionedTransaction> {
+    if bytes.len() > PACKET_DATA_SIZE {
+        return None;
+    }
+    let transaction: VersionedTransaction = bincode::deserialize(bytes).ok()?;
+    // reject partial reads of data in other formats
+    (bincode::serialized_size(&transaction).ok()? == bytes.len() as u64
+        && transaction.sanitize().is_ok())
+    .then_some(transaction)
+}
+
+#[derive(Debug, Serialize)]
+pub struct SignatureVerification {
+    pub signer: PrettyPublicKey,
+    pub signature: String,
+    /// "Valid", "Invalid" or "Missing" for not yet signed transactions
+    pub status: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TransactionSignatures {
+    pub signatures: Vec<SignatureVerification>,
+}
+
+impl TransactionSignatures {
+    pub fn new(transaction: &VersionedTransaction) -> Self {
+        let signers = transaction.message.static_account_keys();
+        TransactionSignatures {
+            signatures: transaction
+                .signatures
+                .iter()
+                .zip(transaction.verify_with_results())
+                .enumerate()
+                .map(|(idx, (signature, is_valid))| SignatureVerification {
+                    signer: signers.get(idx).copied().unwrap_or_default().into(),
+                    signature: signature.to_string(),
+                    status: if *signature == Signature::default() {
+                        "Missing"
+                    } else if is_valid {
+                        "Valid"
+                    } else {
+                        "Invalid"
+                    },
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Output for TransactionSignatures {
+    fn struct_name(&self) -> String {
+        String::from("TransactionSignatures")
+    }
+
+    fn to_raw_struct(&self) -> String {
+        format!("{:#?}", self)
+    }
+
+    fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+/// Accounts of v0 transaction loaded from lookup tables that were not resolved,
+/// instruction accounts that refer to them are marked as unresolved
+#[derive(Debug, Serialize)]
+pub struct UnresolvedLookups {
+    pub accounts: Vec<LookupSource>,
+}
+
+impl Output for UnresolvedLookups {
+    fn struct_name(&self) -> String {
+        String::from("UnresolvedLookups")
+    }
+
+    fn to_raw_struct(&self) -> String {
+        format!("{:#?}", self)
+    }
+
+    fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+/// Decode serialized transaction offline, lookup tables are resolved over RPC only when asked
+pub fn decode_tx(input: &str, resolve_lookups: bool, output_format: OutputFormat) {
+    let transaction = match parse_transaction(input) {
+        Some(transaction) => transaction,
+        None => {
+            print_warning("input is not a base64 or base58 encoded transaction or a file with it");
+            exit(1);
+        }
+    };
+    let loaded_addresses = if resolve_lookups {
+        match resolve_lookup_tables(&transaction.message) {
+            Ok(loaded_addresses) => loaded_addresses,
+            Err(err) => {
+                print_error(err);
+                exit(1);
+            }
+        }
+    } else {
+        None
+    };
+
+    let data = TransactionData {
+        transaction,
+        meta: None,
+        loaded_addresses,
+        slot: None,
+        block_time: None,
+    };
+    let mut page = Page::new(output_format);
+    page.add(TransactionSignatures::new(&data.transaction));
+    data.add_to_page(&mut page, &Default::default());
+    page.display();
+}
@@ -0,0 +1,160 @@
+use num_traits::FromPrimitive;
+use serde::Serialize;
+use solana_sdk::{instruction::InstructionError, pubkey::Pubkey, transaction::TransactionError};
+
+use super::{
+    instruction::{ASSOCIATED_TOKEN_ID, TOKEN_2022_ID},
+    log::{ProgramInvocation, TransactionLogs},
+};
+use crate::{
+    anchor::{error::framework_error, error::ERROR_CODE_OFFSET, idl::fetch_idl},
+    output::Output,
+    pretty::public_key::PrettyPublicKey,
+};
+
+pub const CANDY_MACHINE_CORE_ID: Pubkey =
+    Pubkey::from_str_const("CndyV3LdqHUfDLmE5naZjVN8rBZz4tqhdefbAnjHG3JR");
+
+const ASSOCIATED_TOKEN_ERRORS: &[(u32, &str, &str)] = &[(
+    0,
+    "InvalidOwner",
+    "Associated token account owner does not match address derivation",
+)];
+
+/// source https://github.com/metaplex-foundation/mpl-candy-machine/blob/main/programs/candy-machine-core/src/errors.rs
+const CANDY_MACHINE_CORE_ERRORS: &[(u32, &str, &str)] = &[
+    (6000, "IncorrectOwner", "Account does not have correct owner"),
+    (6001, "Uninitialized", "Account is not initialized"),
+    (6002, "MintMismatch", "Mint Mismatch"),
+    (6003, "IndexGreaterThanLength", "Index greater than length"),
+    (6004, "NumericalOverflowError", "Numerical overflow error"),
+    (6005, "TooManyCreators", "Can only provide up to 4 creators to candy machine (because candy machine is one)"),
+    (6006, "CandyMachineEmpty", "Candy machine is empty"),
+    (6007, "HiddenSettingsDoNotHaveConfigLines", "Candy machines using hidden uris do not have config lines, they have a single hash representing hashed order"),
+    (6008, "CannotChangeNumberOfLines", "Cannot change number of lines unless is a hidden config"),
+    (6009, "CannotSwitchToHiddenSettings", "Cannot switch to hidden settings after items available is greater than 0"),
+    (6010, "IncorrectCollectionAuthority", "Incorrect collection NFT authority"),
+    (6011, "MetadataAccountMustBeEmpty", "The metadata account has data in it, and this must be empty to mint a new NFT"),
+    (6012, "NoChangingCollectionDuringMint", "Can't change collection settings after items have begun to be minted"),
+    (6013, "ExceededLengthError", "Value longer than expected maximum value"),
+    (6014, "MissingConfigLinesSettings", "Missing config lines settings"),
+    (6015, "CannotIncreaseLength", "Cannot increase the length in config lines settings"),
+    (6016, "CannotSwitchFromHiddenSettings", "Cannot switch from hidden settings"),
+    (6017, "CannotChangeSequentialIndexGeneration", "Cannot change sequential index generation after items have begun to be minted"),
+    (6018, "CollectionKeyMismatch", "Collection public key mismatch"),
+    (6019, "CouldNotRetrieveConfigLineData", "Could not retrive config line data"),
+    (6020, "NotFullyLoaded", "Not all config lines were added to the candy machine"),
+];
+
+fn find_error(errors: &[(u32, &str, &str)], code: u32) -> Option<(String, String)> {
+    errors
+        .iter()
+        .find(|(error_code, _, _)| *error_code == code)
+        .map(|(_, name, message)| (name.to_string(), message.to_string()))
+}
+
+fn from_error_enum<E: FromPrimitive + std::fmt::Debug + std::fmt::Display>(
+    code: u32,
+) -> Option<(String, String)> {
+    E::from_u32(code).map(|err| (format!("{:?}", err), err.to_string()))
+}
+
+/// Name and message of a custom program error, built in for well known programs,
+/// errors of Anchor programs, known by the IDL they published on-chain, are Anchor framework
+/// errors or program specific errors looked up in the IDL
+pub fn decode_custom_error(program_id: &Pubkey, code: u32) -> Option<(String, String)> {
+    let builtin = match *program_id {
+        spl_token::ID => from_error_enum::<spl_token::error::TokenError>(code),
+        TOKEN_2022_ID => from_error_enum::<spl_token_2022::error::TokenError>(code),
+        ASSOCIATED_TOKEN_ID => find_error(ASSOCIATED_TOKEN_ERRORS, code),
+        mpl_token_metadata::ID => {
+            from_error_enum::<mpl_token_metadata::errors::MplTokenMetadataError>(code)
+        }
+        mpl_core::ID => from_error_enum::<mpl_core::errors::MplCoreError>(code),
+        CANDY_MACHINE_CORE_ID => find_error(CANDY_MACHINE_CORE_ERRORS, code),
+        _ => None,
+    };
+    if builtin.is_some() {
+        return builtin;
+    }
+    // without the IDL the error is shown as a code
+    let idl = fetch_idl(program_id).ok().flatten()?;
+    if code < ERROR_CODE_OFFSET {
+        return framework_error(code)
+            .map(|(name, message)| (name.to_string(), message.to_string()));
+    }
+    let error = idl.error(code)?;
+    Some((error.name.clone(), error.msg.clone().unwrap_or_default()))
+}
+
+/// Failed instruction of a transaction with the custom program error decoded,
+/// `program` is the program that returned the error, it can be a program invoked through CPI
+#[derive(Debug, Serialize)]
+pub struct InstructionFailure {
+    pub instruction_index: u8,
+    pub program: PrettyPublicKey,
+    pub error: String,
+    pub code: Option<u32>,
+    pub name: Option<String>,
+    pub message: Option<String>,
+}
+
+/// the deepest failed invocation, the program that returned the error
+fn failed_program(invocations: &[ProgramInvocation]) -> Option<&ProgramInvocation> {
+    let failed = invocations.iter().find(|invocation| {
+        invocation
+            .status
+            .as_ref()
+            .is_some_and(|status| status.starts_with("Failed"))
+    })?;
+    failed_program(&failed.invocations).or(Some(failed))
+}
+
+impl InstructionFailure {
+    /// `None` for successful transactions and failures that are not caused by an instruction
+    pub fn new(
+        err: &TransactionError,
+        instruction_programs: &[Pubkey],
+        logs: Option<&TransactionLogs>,
+    ) -> Option<Self> {
+        let TransactionError::InstructionError(instruction_index, instruction_err) = err else {
+            return None;
+        };
+        let program = logs
+            .and_then(|logs| failed_program(&logs.invocations))
+            .map(|invocation| invocation.program)
+            .or_else(|| {
+                instruction_programs
+                    .get(*instruction_index as usize)
+                    .map(|program_id| (*program_id).into())
+            })
+            .unwrap_or_default();
+        let code = match instruction_err {
+            InstructionError::Custom(code) => Some(*code),
+            _ => None,
+        };
+        let decoded = code.and_then(|code| decode_custom_error(&program.pubkey(), code));
+        Some(InstructionFailure {
+            instruction_index: *instruction_index,
+            program,
+            error: instruction_err.to_string(),
+            code,
+            name: decoded.as_ref().map(|(name, _)| name.clone()),
+            message: decoded.map(|(_, message)| message),
+        })
+    }
+}
+
+impl Output for InstructionFailure {
+    fn struct_name(&self) -> String {
+        String::from("InstructionFailure")
+    }
+
+    fn to_raw_struct(&self) -> String {
+        format!("{:#?}", self)
+    }
+
+    fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
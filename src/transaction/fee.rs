@@ -0,0 +1,152 @@
+use std::collections::HashSet;
+
+use serde::Serialize;
+use solana_sdk::{
+    compute_budget, ed25519_program, program_utils::limited_deserialize, pubkey::Pubkey,
+    secp256k1_program, system_instruction::SystemInstruction, system_program,
+};
+
+use super::{
+    instruction::{parse_compute_budget, ComputeBudget},
+    transfer::executed_instructions,
+    TransactionData,
+};
+use crate::{output::Output, pretty::public_key::PrettyPublicKey};
+
+pub const LAMPORTS_PER_SIGNATURE: u64 = 5000;
+/// compute unit limit of an instruction when the transaction doesn't set the limit
+const DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT: u32 = 200_000;
+const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+const MICRO_LAMPORTS_PER_LAMPORT: u128 = 1_000_000;
+
+#[derive(Debug, Serialize)]
+pub struct CreatedAccount {
+    pub pubkey: PrettyPublicKey,
+    pub rent: u64,
+}
+
+/// Transaction fee split into base signature fees and prioritization fee,
+/// all amounts are in lamports and compute unit price is in micro-lamports
+#[derive(Debug, Serialize)]
+pub struct FeeBreakdown {
+    /// transaction signatures and signatures verified by precompiled programs
+    pub signatures: u64,
+    pub base_fee: u64,
+    pub compute_unit_price: u64,
+    /// set by SetComputeUnitLimit or the default limit of 200k per instruction
+    pub compute_unit_limit: u32,
+    pub compute_unit_limit_is_default: bool,
+    pub compute_units_consumed: Option<u64>,
+    pub priority_fee: u64,
+    /// fee charged as recorded in transaction status meta
+    pub fee: Option<u64>,
+    /// accounts created by System program instructions of the transaction, usually rent exempt minimum
+    pub created_accounts: Vec<CreatedAccount>,
+    pub rent: u64,
+}
+
+impl FeeBreakdown {
+    pub fn new(data: &TransactionData, account_keys: &[Pubkey]) -> Self {
+        let message = &data.transaction.message;
+        let program_id = |program_id_index: u8| account_keys.get(program_id_index as usize);
+
+        let mut signatures = message.header().num_required_signatures as u64;
+        let mut compute_unit_price = 0;
+        let mut compute_unit_limit = None;
+        let mut instructions_count = 0;
+        for ix in message.instructions() {
+            match program_id(ix.program_id_index) {
+                Some(program) if *program == compute_budget::ID => {
+                    match parse_compute_budget(&ix.data) {
+                        Some(ComputeBudget::SetComputeUnitPrice(price)) => {
+                            compute_unit_price = price
+                        }
+                        Some(ComputeBudget::SetComputeUnitLimit(limit)) => {
+                            compute_unit_limit = Some(limit)
+                        }
+                        _ => {}
+                    }
+                    continue;
+                }
+                // first byte of precompile instruction data is the number of signatures to verify
+                Some(program)
+                    if *program == ed25519_program::ID || *program == secp256k1_program::ID =>
+                {
+                    signatures += ix.data.first().copied().unwrap_or_default() as u64;
+                }
+                _ => {}
+            }
+            instructions_count += 1;
+        }
+        let compute_unit_limit_is_default = compute_unit_limit.is_none();
+        let compute_unit_limit = compute_unit_limit
+            .unwrap_or(instructions_count * DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT)
+            .min(MAX_COMPUTE_UNIT_LIMIT);
+        let priority_fee = (compute_unit_price as u128 * compute_unit_limit as u128)
+            .div_ceil(MICRO_LAMPORTS_PER_LAMPORT) as u64;
+
+        let meta = data.meta.as_ref();
+        let created_accounts = created_accounts(data, account_keys);
+
+        FeeBreakdown {
+            signatures,
+            base_fee: signatures * LAMPORTS_PER_SIGNATURE,
+            compute_unit_price,
+            compute_unit_limit,
+            compute_unit_limit_is_default,
+            compute_units_consumed: meta
+                .and_then(|meta| Option::from(meta.compute_units_consumed.clone())),
+            priority_fee,
+            fee: meta.map(|meta| meta.fee),
+            rent: created_accounts.iter().map(|account| account.rent).sum(),
+            created_accounts,
+        }
+    }
+}
+
+/// Accounts created by System program CreateAccount, CreateAccountWithSeed and Allocate
+/// instructions, top-level or invoked, with lamports the transaction funded them with,
+/// accounts that only received lamports are not created
+pub fn created_accounts(data: &TransactionData, account_keys: &[Pubkey]) -> Vec<CreatedAccount> {
+    let Some(meta) = &data.meta else {
+        return Vec::new();
+    };
+    let created: HashSet<Pubkey> = executed_instructions(data, account_keys)
+        .into_iter()
+        .filter(|(program_id, _, _)| *program_id == system_program::ID)
+        .filter_map(|(_, accounts, data)| {
+            let idx = match limited_deserialize::<SystemInstruction>(&data).ok()? {
+                SystemInstruction::CreateAccount { .. }
+                | SystemInstruction::CreateAccountWithSeed { .. } => 1,
+                SystemInstruction::Allocate { .. } | SystemInstruction::AllocateWithSeed { .. } => {
+                    0
+                }
+                _ => return None,
+            };
+            accounts.get(idx).copied()
+        })
+        .collect();
+    account_keys
+        .iter()
+        .zip(meta.pre_balances.iter().zip(meta.post_balances.iter()))
+        .filter(|(pubkey, (pre, post))| created.contains(pubkey) && post > pre)
+        .map(|(pubkey, (pre, post))| CreatedAccount {
+            pubkey: (*pubkey).into(),
+            rent: post - pre,
+        })
+        .collect()
+}
+
+impl Output for FeeBreakdown {
+    fn struct_name(&self) -> String {
+        String::from("FeeBreakdown")
+    }
+
+    fn to_raw_struct(&self) -> String {
+        format!("{:#?}", self)
+    }
+
+    fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
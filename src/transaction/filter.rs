@@ -0,0 +1,90 @@
+use solana_client::rpc_response::RpcConfirmedTransactionStatusWithSignature;
+use solana_sdk::{native_token::LAMPORTS_PER_SOL, pubkey::Pubkey};
+
+use super::TransactionData;
+
+/// Filters of account transactions, time range is `since` inclusive and `until` exclusive
+/// unix timestamps compared with transaction block times
+#[derive(Debug, Default)]
+pub struct TransactionsFilter {
+    /// transactions that invoke the program at any depth
+    pub program: Option<Pubkey>,
+    /// only succeeded (true) or only failed (false) transactions
+    pub succeeded: Option<bool>,
+    pub since: Option<i64>,
+    pub until: Option<i64>,
+    /// min absolute SOL balance change of the listed address
+    pub min_sol_change: Option<f64>,
+}
+
+impl TransactionsFilter {
+    pub fn is_empty(&self) -> bool {
+        self.program.is_none()
+            && self.succeeded.is_none()
+            && self.since.is_none()
+            && self.until.is_none()
+            && self.min_sol_change.is_none()
+    }
+
+    /// program and SOL change filters need the whole transaction, others only its signature status
+    pub fn needs_transaction(&self) -> bool {
+        self.program.is_some() || self.min_sol_change.is_some()
+    }
+
+    /// account history goes from the newest transaction, older ones are all out of the range
+    pub fn is_before_range(&self, block_time: Option<i64>) -> bool {
+        matches!((self.since, block_time), (Some(since), Some(time)) if time < since)
+    }
+
+    pub fn matches_status(&self, status: &RpcConfirmedTransactionStatusWithSignature) -> bool {
+        self.matches_result(status.err.is_none()) && self.matches_time(status.block_time)
+    }
+
+    pub fn matches_transaction(&self, data: &TransactionData, address: &Pubkey) -> bool {
+        let succeeded = data.meta.as_ref().map(|meta| meta.err.is_none());
+        if self.succeeded.is_some() && self.succeeded != succeeded {
+            return false;
+        }
+        if !self.matches_time(data.block_time) {
+            return false;
+        }
+        if let Some(program) = &self.program {
+            if !data.invoked_programs().contains(program) {
+                return false;
+            }
+        }
+        if let Some(min_sol_change) = self.min_sol_change {
+            let account_keys = data.account_keys();
+            let change = match (
+                &data.meta,
+                account_keys.iter().position(|key| key == address),
+            ) {
+                (Some(meta), Some(idx)) => {
+                    let pre = meta.pre_balances.get(idx).copied().unwrap_or_default();
+                    let post = meta.post_balances.get(idx).copied().unwrap_or_default();
+                    post.abs_diff(pre)
+                }
+                _ => 0,
+            };
+            if (change as f64) < min_sol_change * LAMPORTS_PER_SOL as f64 {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn matches_result(&self, succeeded: bool) -> bool {
+        self.succeeded.is_none_or(|expected| expected == succeeded)
+    }
+
+    /// transactions without block time are out of any time range
+    fn matches_time(&self, block_time: Option<i64>) -> bool {
+        if self.since.is_none() && self.until.is_none() {
+            return true;
+        }
+        block_time.is_some_and(|time| {
+            self.since.is_none_or(|since| time >= since)
+                && self.until.is_none_or(|until| time < until)
+        })
+    }
+}
@@ -0,0 +1,317 @@
+use solana_sdk::{
+    program_option::COption, program_utils::limited_deserialize, pubkey::Pubkey,
+    system_instruction::SystemInstruction,
+};
+use spl_token::instruction::TokenInstruction;
+
+pub const ASSOCIATED_TOKEN_ID: Pubkey =
+    Pubkey::from_str_const("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+pub const TOKEN_2022_ID: Pubkey =
+    Pubkey::from_str_const("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+pub const MEMO_ID: Pubkey = Pubkey::from_str_const("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+pub const MEMO_V1_ID: Pubkey =
+    Pubkey::from_str_const("Memo1UhkJRfHyvLMcVucJwxXeuD728EqVDDwQDxFMNo");
+
+/// Instruction decoded from its program id and data,
+/// `account_names` are names of instruction accounts in the order program expects them
+pub struct DecodedInstruction {
+    pub name: &'static str,
+    pub account_names: &'static [&'static str],
+    pub args: Vec<(&'static str, String)>,
+}
+
+impl DecodedInstruction {
+    fn new(name: &'static str, account_names: &'static [&'static str]) -> Self {
+        DecodedInstruction {
+            name,
+            account_names,
+            args: Vec::new(),
+        }
+    }
+
+    fn arg(mut self, name: &'static str, value: impl ToString) -> Self {
+        self.args.push((name, value.to_string()));
+        self
+    }
+}
+
+/// Decode instruction of known programs, returns `None` for unknown programs and malformed data
+pub fn decode(program_id: &Pubkey, data: &[u8]) -> Option<DecodedInstruction> {
+    match *program_id {
+        solana_sdk::system_program::ID => decode_system(data),
+        spl_token::ID | TOKEN_2022_ID => decode_token(data),
+        ASSOCIATED_TOKEN_ID => decode_associated_token(data),
+        solana_sdk::compute_budget::ID => decode_compute_budget(data),
+        MEMO_ID | MEMO_V1_ID => decode_memo(data),
+        _ => None,
+    }
+}
+
+fn decode_system(data: &[u8]) -> Option<DecodedInstruction> {
+    let ix = limited_deserialize::<SystemInstruction>(data).ok()?;
+    let decoded = match ix {
+        SystemInstruction::CreateAccount {
+            lamports,
+            space,
+            owner,
+        } => DecodedInstruction::new("CreateAccount", &["funding", "new_account"])
+            .arg("lamports", lamports)
+            .arg("space", space)
+            .arg("owner", owner),
+        SystemInstruction::Assign { owner } => {
+            DecodedInstruction::new("Assign", &["account"]).arg("owner", owner)
+        }
+        SystemInstruction::Transfer { lamports } => {
+            DecodedInstruction::new("Transfer", &["from", "to"]).arg("lamports", lamports)
+        }
+        SystemInstruction::CreateAccountWithSeed {
+            base,
+            seed,
+            lamports,
+            space,
+            owner,
+        } => DecodedInstruction::new("CreateAccountWithSeed", &["funding", "new_account", "base"])
+            .arg("base", base)
+            .arg("seed", seed)
+            .arg("lamports", lamports)
+            .arg("space", space)
+            .arg("owner", owner),
+        SystemInstruction::AdvanceNonceAccount => DecodedInstruction::new(
+            "AdvanceNonceAccount",
+            &["nonce", "recent_blockhashes", "authority"],
+        ),
+        SystemInstruction::WithdrawNonceAccount(lamports) => DecodedInstruction::new(
+            "WithdrawNonceAccount",
+            &["nonce", "to", "recent_blockhashes", "rent", "authority"],
+        )
+        .arg("lamports", lamports),
+        SystemInstruction::InitializeNonceAccount(authority) => DecodedInstruction::new(
+            "InitializeNonceAccount",
+            &["nonce", "recent_blockhashes", "rent"],
+        )
+        .arg("authority", authority),
+        SystemInstruction::AuthorizeNonceAccount(authority) => {
+            DecodedInstruction::new("AuthorizeNonceAccount", &["nonce", "authority"])
+                .arg("new_authority", authority)
+        }
+        SystemInstruction::Allocate { space } => {
+            DecodedInstruction::new("Allocate", &["account"]).arg("space", space)
+        }
+        SystemInstruction::AllocateWithSeed {
+            base,
+            seed,
+            space,
+            owner,
+        } => DecodedInstruction::new("AllocateWithSeed", &["account", "base"])
+            .arg("base", base)
+            .arg("seed", seed)
+            .arg("space", space)
+            .arg("owner", owner),
+        SystemInstruction::AssignWithSeed { base, seed, owner } => {
+            DecodedInstruction::new("AssignWithSeed", &["account", "base"])
+                .arg("base", base)
+                .arg("seed", seed)
+                .arg("owner", owner)
+        }
+        SystemInstruction::TransferWithSeed {
+            lamports,
+            from_seed,
+            from_owner,
+        } => DecodedInstruction::new("TransferWithSeed", &["from", "base", "to"])
+            .arg("lamports", lamports)
+            .arg("from_seed", from_seed)
+            .arg("from_owner", from_owner),
+        SystemInstruction::UpgradeNonceAccount => {
+            DecodedInstruction::new("UpgradeNonceAccount", &["nonce"])
+        }
+    };
+    Some(decoded)
+}
+
+fn coption_to_string(value: COption<Pubkey>) -> String {
+    match value {
+        COption::Some(pubkey) => pubkey.to_string(),
+        COption::None => String::from("None"),
+    }
+}
+
+/// Token-2022 shares instruction layout with Token program,
+/// Token-2022 extension instructions are not decoded
+fn decode_token(data: &[u8]) -> Option<DecodedInstruction> {
+    let ix = TokenInstruction::unpack(data).ok()?;
+    let decoded = match ix {
+        TokenInstruction::InitializeMint {
+            decimals,
+            mint_authority,
+            freeze_authority,
+        } => DecodedInstruction::new("InitializeMint", &["mint", "rent"])
+            .arg("decimals", decimals)
+            .arg("mint_authority", mint_authority)
+            .arg("freeze_authority", coption_to_string(freeze_authority)),
+        TokenInstruction::InitializeAccount => {
+            DecodedInstruction::new("InitializeAccount", &["account", "mint", "owner", "rent"])
+        }
+        TokenInstruction::InitializeMultisig { m } => {
+            DecodedInstruction::new("InitializeMultisig", &["multisig", "rent"]).arg("m", m)
+        }
+        TokenInstruction::Transfer { amount } => {
+            DecodedInstruction::new("Transfer", &["source", "destination", "authority"])
+                .arg("amount", amount)
+        }
+        TokenInstruction::Approve { amount } => {
+            DecodedInstruction::new("Approve", &["source", "delegate", "owner"])
+                .arg("amount", amount)
+        }
+        TokenInstruction::Revoke => DecodedInstruction::new("Revoke", &["source", "owner"]),
+        TokenInstruction::SetAuthority {
+            authority_type,
+            new_authority,
+        } => DecodedInstruction::new("SetAuthority", &["account", "current_authority"])
+            .arg("authority_type", format!("{:?}", authority_type))
+            .arg("new_authority", coption_to_string(new_authority)),
+        TokenInstruction::MintTo { amount } => {
+            DecodedInstruction::new("MintTo", &["mint", "account", "authority"])
+                .arg("amount", amount)
+        }
+        TokenInstruction::Burn { amount } => {
+            DecodedInstruction::new("Burn", &["account", "mint", "authority"]).arg("amount", amount)
+        }
+        TokenInstruction::CloseAccount => {
+            DecodedInstruction::new("CloseAccount", &["account", "destination", "owner"])
+        }
+        TokenInstruction::FreezeAccount => {
+            DecodedInstruction::new("FreezeAccount", &["account", "mint", "authority"])
+        }
+        TokenInstruction::ThawAccount => {
+            DecodedInstruction::new("ThawAccount", &["account", "mint", "authority"])
+        }
+        TokenInstruction::TransferChecked { amount, decimals } => DecodedInstruction::new(
+            "TransferChecked",
+            &["source", "mint", "destination", "authority"],
+        )
+        .arg("amount", amount)
+        .arg("decimals", decimals),
+        TokenInstruction::ApproveChecked { amount, decimals } => {
+            DecodedInstruction::new("ApproveChecked", &["source", "mint", "delegate", "owner"])
+                .arg("amount", amount)
+                .arg("decimals", decimals)
+        }
+        TokenInstruction::MintToChecked { amount, decimals } => {
+            DecodedInstruction::new("MintToChecked", &["mint", "account", "authority"])
+                .arg("amount", amount)
+                .arg("decimals", decimals)
+        }
+        TokenInstruction::BurnChecked { amount, decimals } => {
+            DecodedInstruction::new("BurnChecked", &["account", "mint", "authority"])
+                .arg("amount", amount)
+                .arg("decimals", decimals)
+        }
+        TokenInstruction::InitializeAccount2 { owner } => {
+            DecodedInstruction::new("InitializeAccount2", &["account", "mint", "rent"])
+                .arg("owner", owner)
+        }
+        TokenInstruction::SyncNative => DecodedInstruction::new("SyncNative", &["account"]),
+        TokenInstruction::InitializeAccount3 { owner } => {
+            DecodedInstruction::new("InitializeAccount3", &["account", "mint"]).arg("owner", owner)
+        }
+        TokenInstruction::InitializeMultisig2 { m } => {
+            DecodedInstruction::new("InitializeMultisig2", &["multisig"]).arg("m", m)
+        }
+        TokenInstruction::InitializeMint2 {
+            decimals,
+            mint_authority,
+            freeze_authority,
+        } => DecodedInstruction::new("InitializeMint2", &["mint"])
+            .arg("decimals", decimals)
+            .arg("mint_authority", mint_authority)
+            .arg("freeze_authority", coption_to_string(freeze_authority)),
+        TokenInstruction::GetAccountDataSize => {
+            DecodedInstruction::new("GetAccountDataSize", &["mint"])
+        }
+        TokenInstruction::InitializeImmutableOwner => {
+            DecodedInstruction::new("InitializeImmutableOwner", &["account"])
+        }
+        TokenInstruction::AmountToUiAmount { amount } => {
+            DecodedInstruction::new("AmountToUiAmount", &["mint"]).arg("amount", amount)
+        }
+        TokenInstruction::UiAmountToAmount { ui_amount } => {
+            DecodedInstruction::new("UiAmountToAmount", &["mint"]).arg("ui_amount", ui_amount)
+        }
+    };
+    Some(decoded)
+}
+
+fn decode_associated_token(data: &[u8]) -> Option<DecodedInstruction> {
+    const CREATE_ACCOUNTS: &[&str] = &[
+        "funding",
+        "associated_account",
+        "wallet",
+        "mint",
+        "system_program",
+        "token_program",
+    ];
+    match data {
+        [] | [0] => Some(DecodedInstruction::new("Create", CREATE_ACCOUNTS)),
+        [1] => Some(DecodedInstruction::new("CreateIdempotent", CREATE_ACCOUNTS)),
+        [2] => Some(DecodedInstruction::new(
+            "RecoverNested",
+            &[
+                "nested_account",
+                "nested_mint",
+                "destination",
+                "owner_associated_account",
+                "owner_mint",
+                "wallet",
+                "token_program",
+            ],
+        )),
+        _ => None,
+    }
+}
+
+/// Compute budget instruction, only the ones that affect fees and limits are decoded
+pub enum ComputeBudget {
+    RequestHeapFrame(u32),
+    SetComputeUnitLimit(u32),
+    SetComputeUnitPrice(u64),
+    SetLoadedAccountsDataSizeLimit(u32),
+}
+
+/// Compute budget instructions are borsh encoded: 1 byte tag and a little endian number
+pub fn parse_compute_budget(data: &[u8]) -> Option<ComputeBudget> {
+    let (tag, value) = data.split_first()?;
+    let as_u32 = || Some(u32::from_le_bytes(value.get(..4)?.try_into().ok()?));
+    match tag {
+        1 => as_u32().map(ComputeBudget::RequestHeapFrame),
+        2 => as_u32().map(ComputeBudget::SetComputeUnitLimit),
+        3 => Some(ComputeBudget::SetComputeUnitPrice(u64::from_le_bytes(
+            value.get(..8)?.try_into().ok()?,
+        ))),
+        4 => as_u32().map(ComputeBudget::SetLoadedAccountsDataSizeLimit),
+        _ => None,
+    }
+}
+
+fn decode_compute_budget(data: &[u8]) -> Option<DecodedInstruction> {
+    let decoded = match parse_compute_budget(data)? {
+        ComputeBudget::RequestHeapFrame(bytes) => {
+            DecodedInstruction::new("RequestHeapFrame", &[]).arg("bytes", bytes)
+        }
+        ComputeBudget::SetComputeUnitLimit(units) => {
+            DecodedInstruction::new("SetComputeUnitLimit", &[]).arg("units", units)
+        }
+        ComputeBudget::SetComputeUnitPrice(micro_lamports) => {
+            DecodedInstruction::new("SetComputeUnitPrice", &[])
+                .arg("micro_lamports", micro_lamports)
+        }
+        ComputeBudget::SetLoadedAccountsDataSizeLimit(bytes) => {
+            DecodedInstruction::new("SetLoadedAccountsDataSizeLimit", &[]).arg("bytes", bytes)
+        }
+    };
+    Some(decoded)
+}
+
+fn decode_memo(data: &[u8]) -> Option<DecodedInstruction> {
+    let memo = std::str::from_utf8(data).ok()?;
+    Some(DecodedInstruction::new("Memo", &[]).arg("memo", memo))
+}
@@ -0,0 +1,299 @@
+use std::{cmp::Reverse, collections::HashMap, sync::Arc};
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    anchor::{
+        event::{decode_event, AnchorEvent},
+        idl::Idl,
+    },
+    output::Output,
+    pretty::public_key::PrettyPublicKey,
+};
+
+/// A program invocation parsed from transaction logs with logs it emitted
+/// and programs it invoked through CPI
+#[derive(Debug, Serialize)]
+pub struct ProgramInvocation {
+    pub program: PrettyPublicKey,
+    pub depth: u32,
+    /// `None` when the log is truncated before the invocation result
+    pub status: Option<String>,
+    pub compute_units_consumed: Option<u64>,
+    pub compute_units_available: Option<u64>,
+    /// `Program log:` messages
+    pub logs: Vec<String>,
+    /// `Program data:` base64 encoded payloads that are not decoded as events
+    pub data: Vec<String>,
+    /// `Program data:` payloads decoded as Anchor events with the program IDL
+    pub events: Vec<AnchorEvent>,
+    pub return_data: Option<String>,
+    pub invocations: Vec<ProgramInvocation>,
+}
+
+impl ProgramInvocation {
+    fn new(program: Pubkey, depth: u32) -> Self {
+        ProgramInvocation {
+            program: program.into(),
+            depth,
+            status: None,
+            compute_units_consumed: None,
+            compute_units_available: None,
+            logs: Vec::new(),
+            data: Vec::new(),
+            events: Vec::new(),
+            return_data: None,
+            invocations: Vec::new(),
+        }
+    }
+
+    /// compute units consumed by the program itself without programs it invoked
+    fn own_compute_units(&self) -> u64 {
+        let invoked: u64 = self
+            .invocations
+            .iter()
+            .filter_map(|invocation| invocation.compute_units_consumed)
+            .sum();
+        self.compute_units_consumed
+            .unwrap_or_default()
+            .saturating_sub(invoked)
+    }
+
+    fn decode_events(&mut self, idls: &HashMap<Pubkey, Arc<Idl>>) {
+        if let Some(idl) = idls.get(&self.program.pubkey()) {
+            let mut undecoded = Vec::new();
+            for data in self.data.drain(..) {
+                let event = BASE64
+                    .decode(&data)
+                    .ok()
+                    .and_then(|bytes| decode_event(idl, &bytes));
+                match event {
+                    Some(event) => self.events.push(event),
+                    None => undecoded.push(data),
+                }
+            }
+            self.data = undecoded;
+        }
+        for invocation in &mut self.invocations {
+            invocation.decode_events(idls);
+        }
+    }
+}
+
+/// Program invocations trace parsed from transaction log messages,
+/// `other` keeps lines out of any invocation (e.g. "Log truncated")
+#[derive(Debug, Serialize)]
+pub struct TransactionLogs {
+    pub invocations: Vec<ProgramInvocation>,
+    pub other: Vec<String>,
+}
+
+impl TransactionLogs {
+    pub fn parse(log_messages: &[String]) -> Self {
+        let mut roots: Vec<ProgramInvocation> = Vec::new();
+        let mut stack: Vec<ProgramInvocation> = Vec::new();
+        let mut other: Vec<String> = Vec::new();
+
+        let finish = |stack: &mut Vec<ProgramInvocation>, roots: &mut Vec<ProgramInvocation>| {
+            if let Some(invocation) = stack.pop() {
+                match stack.last_mut() {
+                    Some(caller) => caller.invocations.push(invocation),
+                    None => roots.push(invocation),
+                }
+            }
+        };
+
+        for line in log_messages {
+            if let Some(message) = line.strip_prefix("Program log: ") {
+                match stack.last_mut() {
+                    Some(invocation) => invocation.logs.push(message.to_string()),
+                    None => other.push(line.clone()),
+                }
+                continue;
+            }
+            if let Some(data) = line.strip_prefix("Program data: ") {
+                match stack.last_mut() {
+                    Some(invocation) => invocation.data.push(data.to_string()),
+                    None => other.push(line.clone()),
+                }
+                continue;
+            }
+            if let Some(return_data) = line.strip_prefix("Program return: ") {
+                match (stack.last_mut(), return_data.split_once(' ')) {
+                    (Some(invocation), Some((_, data))) => {
+                        invocation.return_data = Some(data.to_string())
+                    }
+                    _ => other.push(line.clone()),
+                }
+                continue;
+            }
+
+            match parse_program_line(line) {
+                Some(ProgramLine::Invoke(program, depth)) => {
+                    stack.push(ProgramInvocation::new(program, depth));
+                }
+                Some(ProgramLine::Consumed(consumed, available)) => {
+                    if let Some(invocation) = stack.last_mut() {
+                        invocation.compute_units_consumed = Some(consumed);
+                        invocation.compute_units_available = Some(available);
+                    }
+                }
+                Some(ProgramLine::Success) => {
+                    if let Some(invocation) = stack.last_mut() {
+                        invocation.status = Some(String::from("Success"));
+                    }
+                    finish(&mut stack, &mut roots);
+                }
+                Some(ProgramLine::Failed(err)) => {
+                    if let Some(invocation) = stack.last_mut() {
+                        invocation.status = Some(format!("Failed: {}", err));
+                    }
+                    finish(&mut stack, &mut roots);
+                }
+                None => match stack.last_mut() {
+                    Some(invocation) => invocation.logs.push(line.clone()),
+                    None => other.push(line.clone()),
+                },
+            }
+        }
+        // truncated logs leave invocations without a result
+        while !stack.is_empty() {
+            finish(&mut stack, &mut roots);
+        }
+
+        TransactionLogs {
+            invocations: roots,
+            other,
+        }
+    }
+
+    /// programs that emitted `Program data:` payloads, Anchor programs emit events this way
+    pub fn data_programs(&self) -> Vec<Pubkey> {
+        fn collect(invocations: &[ProgramInvocation], programs: &mut Vec<Pubkey>) {
+            for invocation in invocations {
+                let program = invocation.program.pubkey();
+                if !invocation.data.is_empty() && !programs.contains(&program) {
+                    programs.push(program);
+                }
+                collect(&invocation.invocations, programs);
+            }
+        }
+
+        let mut programs = Vec::new();
+        collect(&self.invocations, &mut programs);
+        programs
+    }
+
+    /// decode `Program data:` payloads as events of the IDL of the program that emitted them,
+    /// payloads that are not events of the IDL stay as they are
+    pub fn decode_events(&mut self, idls: &HashMap<Pubkey, Arc<Idl>>) {
+        for invocation in &mut self.invocations {
+            invocation.decode_events(idls);
+        }
+    }
+}
+
+enum ProgramLine<'a> {
+    Invoke(Pubkey, u32),
+    Consumed(u64, u64),
+    Success,
+    Failed(&'a str),
+}
+
+/// parses `Program <ID> invoke [N]`, `Program <ID> consumed X of Y compute units`,
+/// `Program <ID> success` and `Program <ID> failed: <ERR>` lines
+fn parse_program_line(line: &str) -> Option<ProgramLine<'_>> {
+    let (program, rest) = line.strip_prefix("Program ")?.split_once(' ')?;
+    let program = program.parse::<Pubkey>().ok()?;
+    if let Some(depth) = rest.strip_prefix("invoke [") {
+        let depth = depth.strip_suffix(']')?.parse().ok()?;
+        return Some(ProgramLine::Invoke(program, depth));
+    }
+    if let Some(consumed) = rest.strip_prefix("consumed ") {
+        let (consumed, available) = consumed
+            .strip_suffix(" compute units")?
+            .split_once(" of ")?;
+        return Some(ProgramLine::Consumed(
+            consumed.parse().ok()?,
+            available.parse().ok()?,
+        ));
+    }
+    if rest == "success" {
+        return Some(ProgramLine::Success);
+    }
+    rest.strip_prefix("failed: ").map(ProgramLine::Failed)
+}
+
+impl Output for TransactionLogs {
+    fn struct_name(&self) -> String {
+        String::from("TransactionLogs")
+    }
+
+    fn to_raw_struct(&self) -> String {
+        format!("{:#?}", self)
+    }
+
+    fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProgramComputeUnits {
+    pub program: PrettyPublicKey,
+    pub invocations: u32,
+    /// compute units consumed by the program itself, excluding programs it invoked
+    pub compute_units: u64,
+}
+
+/// Compute units per program totals, sorted from the most consuming program
+#[derive(Debug, Serialize)]
+pub struct ComputeUnitsByProgram {
+    pub programs: Vec<ProgramComputeUnits>,
+}
+
+impl ComputeUnitsByProgram {
+    pub fn new(logs: &TransactionLogs) -> Self {
+        fn collect(invocations: &[ProgramInvocation], programs: &mut Vec<ProgramComputeUnits>) {
+            for invocation in invocations {
+                let compute_units = invocation.own_compute_units();
+                match programs
+                    .iter_mut()
+                    .find(|total| total.program == invocation.program)
+                {
+                    Some(total) => {
+                        total.invocations += 1;
+                        total.compute_units += compute_units;
+                    }
+                    None => programs.push(ProgramComputeUnits {
+                        program: invocation.program,
+                        invocations: 1,
+                        compute_units,
+                    }),
+                }
+                collect(&invocation.invocations, programs);
+            }
+        }
+
+        let mut programs = Vec::new();
+        collect(&logs.invocations, &mut programs);
+        programs.sort_by_key(|total| Reverse(total.compute_units));
+        ComputeUnitsByProgram { programs }
+    }
+}
+
+impl Output for ComputeUnitsByProgram {
+    fn struct_name(&self) -> String {
+        String::from("ComputeUnitsByProgram")
+    }
+
+    fn to_raw_struct(&self) -> String {
+        format!("{:#?}", self)
+    }
+
+    fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
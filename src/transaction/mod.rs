@@ -0,0 +1,485 @@
+pub mod balance_change;
+pub mod balance_history;
+pub mod decoder;
+pub mod error;
+pub mod fee;
+pub mod filter;
+pub mod instruction;
+pub mod log;
+pub mod nft;
+pub mod reader;
+pub mod signature;
+pub mod simulator;
+pub mod summary;
+pub mod transfer;
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+
+use serde::Serialize;
+use solana_sdk::{
+    instruction::CompiledInstruction,
+    message::{v0::LoadedAddresses, VersionedMessage},
+    pubkey::Pubkey,
+    signature::Signature,
+    transaction::VersionedTransaction,
+};
+use solana_transaction_status::{
+    option_serializer::OptionSerializer, UiInstruction, UiTransactionStatusMeta,
+};
+
+use self::{
+    balance_change::{BalanceChanges, TokenName},
+    decoder::UnresolvedLookups,
+    error::InstructionFailure,
+    fee::FeeBreakdown,
+    log::{ComputeUnitsByProgram, TransactionLogs},
+    nft::{decode_nft_operations, NftAsset, NftOperations},
+    summary::TransactionSummary,
+};
+use crate::{
+    anchor::idl::Idl,
+    output::Output,
+    page::Page,
+    pretty::{public_key::PrettyPublicKey, time::PrettyTimestamp},
+};
+
+/// Transaction and its status meta, a source of all transaction page parts,
+/// meta is `None` for transactions that were not executed (e.g. decoded offline),
+/// `loaded_addresses` are addresses of v0 transactions resolved from lookup tables
+pub struct TransactionData {
+    pub transaction: VersionedTransaction,
+    pub meta: Option<UiTransactionStatusMeta>,
+    pub loaded_addresses: Option<LoadedAddresses>,
+    pub slot: Option<u64>,
+    pub block_time: Option<i64>,
+}
+
+/// Addresses loaded from lookup tables as recorded in the transaction status meta
+pub fn meta_loaded_addresses(meta: &UiTransactionStatusMeta) -> Option<LoadedAddresses> {
+    let OptionSerializer::Some(loaded) = &meta.loaded_addresses else {
+        return None;
+    };
+    let parse = |keys: &[String]| keys.iter().filter_map(|key| key.parse().ok()).collect();
+    Some(LoadedAddresses {
+        writable: parse(&loaded.writable),
+        readonly: parse(&loaded.readonly),
+    })
+}
+
+/// Data fetched over RPC to describe the transaction, `token_names` name mints
+/// in token balance changes, `nft_assets` are NFTs the transaction operates on
+/// and `idls` are IDLs of Anchor programs that emitted events
+#[derive(Default)]
+pub struct TransactionContext {
+    pub token_names: HashMap<Pubkey, TokenName>,
+    pub nft_assets: HashMap<Pubkey, NftAsset>,
+    pub idls: HashMap<Pubkey, Arc<Idl>>,
+}
+
+impl TransactionData {
+    /// All account keys of the transaction, static keys followed by
+    /// addresses loaded from lookup tables (writable first, then readonly)
+    pub fn account_keys(&self) -> Vec<Pubkey> {
+        let mut keys = self.transaction.message.static_account_keys().to_vec();
+        if let Some(loaded) = &self.loaded_addresses {
+            keys.extend(loaded.writable.iter().chain(loaded.readonly.iter()));
+        }
+        keys
+    }
+
+    /// Programs invoked by the transaction instructions and by CPIs at any depth
+    pub fn invoked_programs(&self) -> Vec<Pubkey> {
+        let account_keys = self.account_keys();
+        let outer = self
+            .transaction
+            .message
+            .instructions()
+            .iter()
+            .map(|ix| ix.program_id_index);
+        let inner = match self.meta.as_ref().map(|meta| &meta.inner_instructions) {
+            Some(OptionSerializer::Some(inner_instructions)) => inner_instructions
+                .iter()
+                .flat_map(|inner| inner.instructions.iter())
+                .filter_map(|ix| match ix {
+                    UiInstruction::Compiled(ix) => Some(ix.program_id_index),
+                    UiInstruction::Parsed(_) => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        let mut programs: Vec<Pubkey> = outer
+            .chain(inner)
+            .filter_map(|idx| account_keys.get(idx as usize).copied())
+            .collect();
+        programs.sort();
+        programs.dedup();
+        programs
+    }
+
+    /// Lookup table address and index in the table for each loaded address,
+    /// in the same order as loaded addresses follow static keys
+    pub fn lookup_sources(&self) -> Vec<LookupSource> {
+        let lookups = self
+            .transaction
+            .message
+            .address_table_lookups()
+            .unwrap_or_default();
+        let writable = lookups.iter().flat_map(|lookup| {
+            lookup.writable_indexes.iter().map(|index| LookupSource {
+                table: lookup.account_key.into(),
+                index: *index,
+            })
+        });
+        let readonly = lookups.iter().flat_map(|lookup| {
+            lookup.readonly_indexes.iter().map(|index| LookupSource {
+                table: lookup.account_key.into(),
+                index: *index,
+            })
+        });
+        writable.chain(readonly).collect()
+    }
+
+    /// Instructions invoked by the outer instruction at `outer_idx` (CPIs),
+    /// nested by their stack height
+    fn inner_instructions(&self, outer_idx: usize, account_keys: &[Pubkey]) -> Vec<Instruction> {
+        let Some(meta) = &self.meta else {
+            return Vec::new();
+        };
+        let OptionSerializer::Some(inner_instructions) = &meta.inner_instructions else {
+            return Vec::new();
+        };
+        let Some(inner) = inner_instructions
+            .iter()
+            .find(|inner| inner.index as usize == outer_idx)
+        else {
+            return Vec::new();
+        };
+        let mut invoked: Vec<(u32, Instruction)> = inner
+            .instructions
+            .iter()
+            .filter_map(|ix| match ix {
+                UiInstruction::Compiled(ix) => Some(ix),
+                UiInstruction::Parsed(_) => None,
+            })
+            .map(|ix| {
+                let data = bs58::decode(&ix.data).into_vec().unwrap_or_default();
+                (
+                    // stack height is not recorded for old transactions, nest them under the outer one
+                    ix.stack_height.unwrap_or(2),
+                    Instruction::from_parts(ix.program_id_index, &ix.accounts, &data, account_keys),
+                )
+            })
+            .collect();
+        invoked.reverse();
+        nest_instructions(&mut invoked, 2)
+    }
+
+    /// program invocations parsed from log messages of the transaction meta
+    pub fn logs(&self) -> Option<TransactionLogs> {
+        match &self.meta.as_ref()?.log_messages {
+            OptionSerializer::Some(log_messages) => Some(TransactionLogs::parse(log_messages)),
+            _ => None,
+        }
+    }
+
+    /// add transaction parts to the page, NFT operations, token balance changes
+    /// and events in logs are described with the data of `context`
+    pub fn add_to_page(&self, page: &mut Page, context: &TransactionContext) {
+        let account_keys = self.account_keys();
+        let mut logs = self.logs();
+        if let Some(logs) = &mut logs {
+            logs.decode_events(&context.idls);
+        }
+        page.add(TransactionSummary::new(
+            self,
+            &account_keys,
+            &context.token_names,
+        ));
+        page.add(TransactionHeader::new(self));
+        let nft_operations = NftOperations::new(
+            &decode_nft_operations(self, &account_keys),
+            &context.nft_assets,
+        );
+        if !nft_operations.operations.is_empty() {
+            page.add(nft_operations);
+        }
+        if let Some(Err(err)) = self.meta.as_ref().map(|meta| &meta.status) {
+            let instruction_programs: Vec<Pubkey> = self
+                .transaction
+                .message
+                .instructions()
+                .iter()
+                .map(|ix| *ix.program_id(&account_keys))
+                .collect();
+            if let Some(failure) =
+                InstructionFailure::new(err, &instruction_programs, logs.as_ref())
+            {
+                page.add(failure);
+            }
+        }
+        page.add(FeeBreakdown::new(self, &account_keys));
+        page.add(TransactionAccounts::new(
+            &self.transaction.message,
+            &account_keys,
+            self.lookup_sources(),
+        ));
+        page.add(TransactionInstructions {
+            instructions: self
+                .transaction
+                .message
+                .instructions()
+                .iter()
+                .enumerate()
+                .map(|(idx, ix)| {
+                    let mut instruction = Instruction::new(ix, &account_keys);
+                    instruction.inner = self.inner_instructions(idx, &account_keys);
+                    instruction
+                })
+                .collect(),
+        });
+        if let Some(meta) = &self.meta {
+            page.add(BalanceChanges::new(
+                meta,
+                &account_keys,
+                &context.token_names,
+            ));
+        }
+        if let Some(logs) = logs {
+            page.add(ComputeUnitsByProgram::new(&logs));
+            page.add(logs);
+        }
+        if self.loaded_addresses.is_none() {
+            let accounts = self.lookup_sources();
+            if !accounts.is_empty() {
+                page.add(UnresolvedLookups { accounts });
+            }
+        }
+    }
+}
+
+/// Takes instructions from the end of `invoked` (reversed call order) while they are
+/// at `stack_height` or deeper, deeper instructions become inner instructions of the previous one
+fn nest_instructions(invoked: &mut Vec<(u32, Instruction)>, stack_height: u32) -> Vec<Instruction> {
+    let mut instructions: Vec<Instruction> = Vec::new();
+    while let Some((height, _)) = invoked.last() {
+        if *height < stack_height {
+            break;
+        }
+        match instructions.last_mut() {
+            Some(caller) if *height > stack_height => {
+                caller.inner = nest_instructions(invoked, stack_height + 1);
+            }
+            _ => {
+                let (_, instruction) = invoked.pop().unwrap();
+                instructions.push(instruction);
+            }
+        }
+    }
+    instructions
+}
+
+#[derive(Debug, Serialize)]
+pub struct TransactionHeader {
+    pub signature: Option<String>,
+    pub version: String,
+    pub slot: Option<u64>,
+    pub block_time: Option<PrettyTimestamp>,
+    pub status: Option<String>,
+    pub fee: Option<u64>,
+    pub compute_units_consumed: Option<u64>,
+}
+
+impl TransactionHeader {
+    pub fn new(data: &TransactionData) -> Self {
+        let signature = data
+            .transaction
+            .signatures
+            .first()
+            .filter(|sig| **sig != Signature::default())
+            .map(|sig| sig.to_string());
+        let version = match data.transaction.message {
+            VersionedMessage::Legacy(_) => String::from("legacy"),
+            VersionedMessage::V0(_) => String::from("0"),
+        };
+        let meta = data.meta.as_ref();
+        TransactionHeader {
+            signature,
+            version,
+            slot: data.slot,
+            block_time: data.block_time.map(PrettyTimestamp::from),
+            status: meta.map(|meta| match &meta.status {
+                Ok(()) => String::from("Success"),
+                Err(err) => format!("Failed: {}", err),
+            }),
+            fee: meta.map(|meta| meta.fee),
+            compute_units_consumed: meta
+                .and_then(|meta| Option::from(meta.compute_units_consumed.clone())),
+        }
+    }
+}
+
+impl Output for TransactionHeader {
+    fn struct_name(&self) -> String {
+        String::from("TransactionHeader")
+    }
+
+    fn to_raw_struct(&self) -> String {
+        format!("{:#?}", self)
+    }
+
+    fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+/// Where an account of v0 transaction is loaded from
+#[derive(Debug, Serialize)]
+pub struct LookupSource {
+    pub table: PrettyPublicKey,
+    pub index: u8,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TransactionAccount {
+    pub pubkey: PrettyPublicKey,
+    pub signer: bool,
+    pub writable: bool,
+    /// `None` for static account keys
+    pub lookup: Option<LookupSource>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TransactionAccounts {
+    pub accounts: Vec<TransactionAccount>,
+}
+
+impl TransactionAccounts {
+    pub fn new(
+        message: &VersionedMessage,
+        account_keys: &[Pubkey],
+        lookup_sources: Vec<LookupSource>,
+    ) -> Self {
+        let static_keys_count = message.static_account_keys().len();
+        let mut lookup_sources = lookup_sources.into_iter();
+        TransactionAccounts {
+            accounts: account_keys
+                .iter()
+                .enumerate()
+                .map(|(idx, pubkey)| TransactionAccount {
+                    pubkey: (*pubkey).into(),
+                    signer: message.is_signer(idx),
+                    writable: message.is_maybe_writable(idx, None),
+                    lookup: if idx < static_keys_count {
+                        None
+                    } else {
+                        lookup_sources.next()
+                    },
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Output for TransactionAccounts {
+    fn struct_name(&self) -> String {
+        String::from("TransactionAccounts")
+    }
+
+    fn to_raw_struct(&self) -> String {
+        format!("{:#?}", self)
+    }
+
+    fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct InstructionAccount {
+    pub name: String,
+    pub pubkey: PrettyPublicKey,
+}
+
+/// Instruction with accounts and arguments decoded for known programs,
+/// raw `data` (base58) is kept for instructions that can't be decoded
+#[derive(Debug, Serialize)]
+pub struct Instruction {
+    pub program: PrettyPublicKey,
+    pub name: Option<&'static str>,
+    pub accounts: Vec<InstructionAccount>,
+    pub args: BTreeMap<&'static str, String>,
+    pub data: Option<String>,
+    /// instructions invoked by this instruction through CPI
+    pub inner: Vec<Instruction>,
+}
+
+impl Instruction {
+    pub fn new(ix: &CompiledInstruction, account_keys: &[Pubkey]) -> Self {
+        Instruction::from_parts(ix.program_id_index, &ix.accounts, &ix.data, account_keys)
+    }
+
+    pub fn from_parts(
+        program_id_index: u8,
+        accounts: &[u8],
+        data: &[u8],
+        account_keys: &[Pubkey],
+    ) -> Self {
+        let key_at = |idx: u8| account_keys.get(idx as usize).copied().unwrap_or_default();
+        let program_id = key_at(program_id_index);
+        let decoded = instruction::decode(&program_id, data);
+        let account_names = decoded.as_ref().map_or(&[][..], |d| d.account_names);
+        Instruction {
+            program: program_id.into(),
+            name: decoded.as_ref().map(|d| d.name),
+            accounts: accounts
+                .iter()
+                .enumerate()
+                .map(|(pos, idx)| {
+                    let name = account_names
+                        .get(pos)
+                        .map_or_else(|| format!("#{}", pos), |name| name.to_string());
+                    InstructionAccount {
+                        // accounts from lookup tables are unknown until tables are resolved
+                        name: if (*idx as usize) < account_keys.len() {
+                            name
+                        } else {
+                            format!("{} (unresolved lookup)", name)
+                        },
+                        pubkey: key_at(*idx).into(),
+                    }
+                })
+                .collect(),
+            data: if decoded.is_some() {
+                None
+            } else {
+                Some(bs58::encode(data).into_string())
+            },
+            args: decoded
+                .map(|d| d.args.into_iter().collect())
+                .unwrap_or_default(),
+            inner: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct TransactionInstructions {
+    pub instructions: Vec<Instruction>,
+}
+
+impl Output for TransactionInstructions {
+    fn struct_name(&self) -> String {
+        String::from("TransactionInstructions")
+    }
+
+    fn to_raw_struct(&self) -> String {
+        format!("{:#?}", self)
+    }
+
+    fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
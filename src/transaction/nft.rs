@@ -0,0 +1,670 @@
+use std::{collections::HashMap, fmt};
+
+use borsh::BorshDeserialize;
+use mpl_core::{
+    instructions::{
+        AddPluginV1InstructionArgs, ApprovePluginAuthorityV1InstructionArgs,
+        CreateV1InstructionArgs, CreateV2InstructionArgs, RevokePluginAuthorityV1InstructionArgs,
+        UpdatePluginV1InstructionArgs,
+    },
+    types::{Plugin, PluginAuthority, PluginType, UpdateAuthority},
+};
+use mpl_token_metadata::{
+    instructions::{CreateInstructionArgs, CreateMetadataAccountV3InstructionArgs},
+    types::{CreateArgs, TokenStandard},
+};
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+use spl_token::instruction::TokenInstruction;
+
+use super::{
+    instruction::TOKEN_2022_ID,
+    transfer::{executed_instructions, token_accounts, TokenAccountInfo},
+    TransactionData,
+};
+use crate::{
+    anchor::instruction_discriminator,
+    asset::find_das_asset,
+    metaplex::{
+        bubblegum::{find_asset_id, leaf_schema_asset_id, BUBBLEGUM_ID, NOOP_ID},
+        das::Asset,
+    },
+    output::Output,
+    pretty::public_key::PrettyPublicKey,
+    rpc,
+};
+
+/// `getMultipleAccounts` returns up to 100 accounts
+const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NftOperationKind {
+    Mint,
+    Transfer,
+    Burn,
+    Delegate,
+    Revoke,
+    Lock,
+    Unlock,
+}
+
+impl fmt::Display for NftOperationKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NftOperationKind::Mint => write!(f, "mint"),
+            NftOperationKind::Transfer => write!(f, "transfer"),
+            NftOperationKind::Burn => write!(f, "burn"),
+            NftOperationKind::Delegate => write!(f, "delegate"),
+            NftOperationKind::Revoke => write!(f, "revoke"),
+            NftOperationKind::Lock => write!(f, "lock"),
+            NftOperationKind::Unlock => write!(f, "unlock"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub enum NftStandard {
+    #[serde(rename = "Token Metadata")]
+    TokenMetadata,
+    #[serde(rename = "pNFT")]
+    Programmable,
+    Core,
+    #[serde(rename = "cNFT")]
+    Compressed,
+}
+
+impl fmt::Display for NftStandard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NftStandard::TokenMetadata => write!(f, "Token Metadata"),
+            NftStandard::Programmable => write!(f, "pNFT"),
+            NftStandard::Core => write!(f, "Core"),
+            NftStandard::Compressed => write!(f, "cNFT"),
+        }
+    }
+}
+
+/// NFT fetched for the transaction page, from its account or DAS API if the account is closed
+#[derive(Debug)]
+pub struct NftAsset {
+    pub name: String,
+    pub collection: Option<Pubkey>,
+    pub standard: NftStandard,
+}
+
+impl NftAsset {
+    fn from_das(asset: Asset) -> Option<Self> {
+        let standard = match asset.interface.as_str() {
+            "V1_NFT" | "LEGACY_NFT" if asset.compression.compressed => NftStandard::Compressed,
+            "V1_NFT" | "LEGACY_NFT" => NftStandard::TokenMetadata,
+            "ProgrammableNFT" => NftStandard::Programmable,
+            "MplCoreAsset" => NftStandard::Core,
+            _ => return None,
+        };
+        Some(NftAsset {
+            collection: asset
+                .collection()
+                .and_then(|collection| collection.parse().ok()),
+            name: asset.content.metadata.name,
+            standard,
+        })
+    }
+}
+
+/// NFT operation decoded from an instruction, `asset` is the mint of Token Metadata NFTs
+/// and the asset id of Core assets and cNFTs
+#[derive(Debug)]
+pub struct DecodedNftOperation {
+    pub kind: NftOperationKind,
+    pub standard: NftStandard,
+    /// `None` for minted cNFTs when the transaction has no inner instructions
+    /// with their leaf schema, e.g. a simulation that failed
+    pub asset: Option<Pubkey>,
+    pub name: Option<String>,
+    pub collection: Option<Pubkey>,
+    pub from: Option<Pubkey>,
+    pub to: Option<Pubkey>,
+    /// made by Token program which doesn't tell NFTs from other tokens
+    pub by_token_program: bool,
+    /// decoded from an instruction that works for fungible tokens too,
+    /// shown only if the fetched asset is an NFT
+    pub unconfirmed: bool,
+}
+
+impl DecodedNftOperation {
+    fn new(kind: NftOperationKind, standard: NftStandard, asset: Pubkey) -> Self {
+        DecodedNftOperation {
+            kind,
+            standard,
+            asset: Some(asset),
+            name: None,
+            collection: None,
+            from: None,
+            to: None,
+            by_token_program: false,
+            unconfirmed: false,
+        }
+    }
+
+    fn with_from(mut self, from: Option<Pubkey>) -> Self {
+        self.from = from;
+        self
+    }
+
+    fn with_to(mut self, to: Option<Pubkey>) -> Self {
+        self.to = to;
+        self
+    }
+
+    fn unconfirmed(mut self) -> Self {
+        self.unconfirmed = true;
+        self
+    }
+}
+
+/// NFT minted, transferred, burned, delegated or locked by the transaction
+#[derive(Debug, Serialize)]
+pub struct NftOperation {
+    pub operation: NftOperationKind,
+    pub standard: NftStandard,
+    pub asset: Option<PrettyPublicKey>,
+    pub name: Option<String>,
+    pub collection: Option<PrettyPublicKey>,
+    /// owner of the NFT, or the authority acting for the owner
+    pub from: Option<PrettyPublicKey>,
+    /// new owner or delegate
+    pub to: Option<PrettyPublicKey>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NftOperations {
+    pub operations: Vec<NftOperation>,
+}
+
+impl NftOperations {
+    /// operations with names and collections of fetched assets, operations that need
+    /// the asset to be confirmed as an NFT are dropped when it is not fetched
+    pub fn new(decoded: &[DecodedNftOperation], nft_assets: &HashMap<Pubkey, NftAsset>) -> Self {
+        let operations = decoded
+            .iter()
+            .filter_map(|operation| {
+                let fetched = operation.asset.and_then(|asset| nft_assets.get(&asset));
+                if operation.unconfirmed && fetched.is_none() {
+                    return None;
+                }
+                let name = fetched
+                    .map(|asset| asset.name.clone())
+                    .or_else(|| operation.name.clone())
+                    .map(|name| name.trim_end_matches('\0').to_string());
+                Some(NftOperation {
+                    operation: operation.kind,
+                    // pNFTs are told from other Token Metadata NFTs by the fetched metadata
+                    standard: match (operation.standard, fetched) {
+                        (NftStandard::TokenMetadata, Some(asset)) => asset.standard,
+                        _ => operation.standard,
+                    },
+                    asset: operation.asset.map(PrettyPublicKey::from),
+                    name,
+                    collection: fetched
+                        .and_then(|asset| asset.collection)
+                        .or(operation.collection)
+                        .map(PrettyPublicKey::from),
+                    from: operation.from.map(PrettyPublicKey::from),
+                    to: operation.to.map(PrettyPublicKey::from),
+                })
+            })
+            .collect();
+        NftOperations { operations }
+    }
+}
+
+impl Output for NftOperations {
+    fn struct_name(&self) -> String {
+        String::from("NftOperations")
+    }
+
+    fn to_raw_struct(&self) -> String {
+        format!("{:#?}", self)
+    }
+
+    fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+/// NFT operations of the executed instructions. Token Metadata instructions invoke
+/// Token program, its instructions are kept only for mints no Metaplex instruction touched
+pub fn decode_nft_operations(
+    data: &TransactionData,
+    account_keys: &[Pubkey],
+) -> Vec<DecodedNftOperation> {
+    let instructions = executed_instructions(data, account_keys);
+    let token_accounts = token_accounts(data, account_keys);
+    let master_edition_mints: Vec<Pubkey> = instructions
+        .iter()
+        .filter(|(program_id, _, data)| {
+            *program_id == mpl_token_metadata::ID && data.first() == Some(&17)
+        })
+        .filter_map(|(_, accounts, _)| accounts.get(1).copied())
+        .collect();
+    let operations: Vec<DecodedNftOperation> = instructions
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, (program_id, accounts, data))| match *program_id {
+            mpl_token_metadata::ID => {
+                decode_token_metadata(accounts, data, &token_accounts, &master_edition_mints)
+            }
+            mpl_core::ID => decode_core(accounts, data),
+            BUBBLEGUM_ID => decode_bubblegum(accounts, data, &instructions[idx + 1..]),
+            spl_token::ID | TOKEN_2022_ID => decode_token(accounts, data, &token_accounts),
+            _ => None,
+        })
+        .collect();
+
+    // NFT created by Token Metadata is minted to its owner by a separate instruction
+    let created: Vec<Option<Pubkey>> = operations
+        .iter()
+        .filter(|operation| operation.kind == NftOperationKind::Mint && !operation.unconfirmed)
+        .map(|operation| operation.asset)
+        .collect();
+    let (minted, mut operations): (Vec<_>, Vec<_>) =
+        operations.into_iter().partition(|operation| {
+            operation.kind == NftOperationKind::Mint
+                && operation.unconfirmed
+                && created.contains(&operation.asset)
+        });
+    for minted in minted {
+        if let Some(created) = operations.iter_mut().find(|operation| {
+            operation.kind == NftOperationKind::Mint && operation.asset == minted.asset
+        }) {
+            created.to = created.to.or(minted.to);
+        }
+    }
+    let metaplex_assets: Vec<Option<Pubkey>> = operations
+        .iter()
+        .filter(|operation| !operation.by_token_program)
+        .map(|operation| operation.asset)
+        .collect();
+    operations.retain(|operation| {
+        !operation.by_token_program || !metaplex_assets.contains(&operation.asset)
+    });
+    operations
+}
+
+/// owner of the token account if it is known, otherwise the account itself
+fn token_owner(token_accounts: &HashMap<Pubkey, TokenAccountInfo>, account: &Pubkey) -> Pubkey {
+    token_accounts
+        .get(account)
+        .and_then(|info| info.owner)
+        .unwrap_or(*account)
+}
+
+/// optional accounts of Metaplex instructions are set to the program id when they are omitted
+fn optional_account(accounts: &[Pubkey], idx: usize, program_id: &Pubkey) -> Option<Pubkey> {
+    accounts
+        .get(idx)
+        .copied()
+        .filter(|account| account != program_id)
+}
+
+fn decode_token_metadata(
+    accounts: &[Pubkey],
+    data: &[u8],
+    token_accounts: &HashMap<Pubkey, TokenAccountInfo>,
+    master_edition_mints: &[Pubkey],
+) -> Option<DecodedNftOperation> {
+    use NftOperationKind::*;
+    let program_id = &mpl_token_metadata::ID;
+    let account = |idx: usize| accounts.get(idx).copied();
+    let owner_of = |idx: usize| account(idx).map(|account| token_owner(token_accounts, &account));
+    let standard = NftStandard::TokenMetadata;
+    let operation = match data.first()? {
+        // Create
+        42 => {
+            let args = CreateInstructionArgs::deserialize(&mut data.get(1..)?).ok()?;
+            let CreateArgs::V1 {
+                name,
+                token_standard,
+                collection,
+                ..
+            } = args.create_args;
+            let standard = match token_standard {
+                TokenStandard::NonFungible | TokenStandard::NonFungibleEdition => standard,
+                TokenStandard::ProgrammableNonFungible
+                | TokenStandard::ProgrammableNonFungibleEdition => NftStandard::Programmable,
+                _ => return None,
+            };
+            let mut operation = DecodedNftOperation::new(Mint, standard, account(2)?);
+            operation.name = Some(name);
+            operation.collection = collection.map(|collection| collection.key);
+            operation
+        }
+        // CreateMetadataAccountV3 of a mint with a master edition
+        33 => {
+            let mint = account(1)?;
+            if !master_edition_mints.contains(&mint) {
+                return None;
+            }
+            let args =
+                CreateMetadataAccountV3InstructionArgs::deserialize(&mut data.get(1..)?).ok()?;
+            let mut operation = DecodedNftOperation::new(Mint, standard, mint);
+            operation.name = Some(args.data.name);
+            operation.collection = args.data.collection.map(|collection| collection.key);
+            operation
+        }
+        // Mint
+        43 => DecodedNftOperation::new(Mint, standard, account(5)?)
+            .with_to(optional_account(accounts, 1, program_id).or_else(|| owner_of(0)))
+            .unconfirmed(),
+        // Transfer
+        49 => DecodedNftOperation::new(Transfer, standard, account(4)?)
+            .with_from(account(1))
+            .with_to(account(3))
+            .unconfirmed(),
+        // Burn
+        41 => DecodedNftOperation::new(Burn, standard, account(4)?)
+            .with_from(owner_of(5))
+            .unconfirmed(),
+        // BurnNft
+        29 => DecodedNftOperation::new(Burn, standard, account(2)?).with_from(account(1)),
+        // Delegate and Revoke
+        44 | 45 => {
+            let kind = if data[0] == 44 { Delegate } else { Revoke };
+            DecodedNftOperation::new(kind, standard, account(5)?)
+                .with_from(account(7))
+                .with_to(account(1))
+                .unconfirmed()
+        }
+        // Lock and Unlock
+        46 | 47 => {
+            let kind = if data[0] == 46 { Lock } else { Unlock };
+            DecodedNftOperation::new(kind, standard, account(3)?)
+                .with_from(optional_account(accounts, 1, program_id).or_else(|| owner_of(2)))
+                .unconfirmed()
+        }
+        // FreezeDelegatedAccount and ThawDelegatedAccount
+        26 | 27 => {
+            let kind = if data[0] == 26 { Lock } else { Unlock };
+            DecodedNftOperation::new(kind, standard, account(3)?).with_from(owner_of(1))
+        }
+        _ => return None,
+    };
+    Some(operation)
+}
+
+fn decode_core(accounts: &[Pubkey], data: &[u8]) -> Option<DecodedNftOperation> {
+    use NftOperationKind::*;
+    let program_id = &mpl_core::ID;
+    let optional = |idx: usize| optional_account(accounts, idx, program_id);
+    let args = data.get(1..)?;
+    let is_delegate = |plugin_type: &PluginType| {
+        matches!(
+            plugin_type,
+            PluginType::TransferDelegate | PluginType::BurnDelegate | PluginType::FreezeDelegate
+        )
+    };
+    let delegate_address = |authority: &PluginAuthority| match authority {
+        PluginAuthority::Address { address } => Some(*address),
+        _ => None,
+    };
+    // owner, or a delegate, signs as the authority, payer is the authority when it is omitted
+    let authority = optional(3).or(optional(2));
+    let (kind, from, to, name) = match data.first()? {
+        // CreateV1 and CreateV2, owner is the authority when it is omitted
+        // and the authority is the payer
+        0 | 20 => {
+            let name = match data[0] {
+                0 => CreateV1InstructionArgs::deserialize(&mut &args[..]).map(|args| args.name),
+                _ => CreateV2InstructionArgs::deserialize(&mut &args[..]).map(|args| args.name),
+            };
+            let name = name.ok()?;
+            let owner = optional(4).or(optional(2)).or(optional(3));
+            (Mint, None, owner, Some(name))
+        }
+        // TransferV1
+        14 => (Transfer, authority, optional(4), None),
+        // BurnV1
+        12 => (Burn, authority, None, None),
+        // AddPluginV1
+        2 => {
+            let args = AddPluginV1InstructionArgs::deserialize(&mut &args[..]).ok()?;
+            match args.plugin {
+                Plugin::FreezeDelegate(freeze) if freeze.frozen => (Lock, authority, None, None),
+                Plugin::FreezeDelegate(_)
+                | Plugin::TransferDelegate(_)
+                | Plugin::BurnDelegate(_) => {
+                    let to = args.init_authority.as_ref().and_then(delegate_address);
+                    (Delegate, authority, Some(to?), None)
+                }
+                _ => return None,
+            }
+        }
+        // UpdatePluginV1
+        6 => match UpdatePluginV1InstructionArgs::deserialize(&mut &args[..])
+            .ok()?
+            .plugin
+        {
+            Plugin::FreezeDelegate(freeze) if freeze.frozen => (Lock, authority, None, None),
+            Plugin::FreezeDelegate(_) => (Unlock, authority, None, None),
+            _ => return None,
+        },
+        // ApprovePluginAuthorityV1
+        8 => {
+            let args = ApprovePluginAuthorityV1InstructionArgs::deserialize(&mut &args[..]).ok()?;
+            if !is_delegate(&args.plugin_type) {
+                return None;
+            }
+            let to = delegate_address(&args.new_authority);
+            (Delegate, authority, to, None)
+        }
+        // RevokePluginAuthorityV1
+        10 => {
+            let args = RevokePluginAuthorityV1InstructionArgs::deserialize(&mut &args[..]).ok()?;
+            if !is_delegate(&args.plugin_type) {
+                return None;
+            }
+            (Revoke, authority, None, None)
+        }
+        _ => return None,
+    };
+    let mut operation = DecodedNftOperation::new(kind, NftStandard::Core, *accounts.first()?)
+        .with_from(from)
+        .with_to(to);
+    operation.name = name;
+    operation.collection = optional(1);
+    Some(operation)
+}
+
+/// `following` are the instructions executed after this one, a mint invokes Noop
+/// program with the leaf schema of the minted cNFT before the next Bubblegum instruction
+fn decode_bubblegum(
+    accounts: &[Pubkey],
+    data: &[u8],
+    following: &[(Pubkey, Vec<Pubkey>, Vec<u8>)],
+) -> Option<DecodedNftOperation> {
+    use NftOperationKind::*;
+    let discriminator = data.get(..8)?;
+    let is = |name: &str| discriminator == instruction_discriminator(name);
+    let account = |idx: usize| accounts.get(idx).copied();
+    if is("mint_v1") || is("mint_to_collection_v1") {
+        // metadata args start with the name
+        let name = String::deserialize(&mut data.get(8..)?).ok()?;
+        let collection = match is("mint_to_collection_v1") {
+            true => account(8),
+            false => None,
+        };
+        let asset = following
+            .iter()
+            .take_while(|(program_id, _, _)| *program_id != BUBBLEGUM_ID)
+            .filter(|(program_id, _, _)| *program_id == NOOP_ID)
+            .find_map(|(_, _, data)| leaf_schema_asset_id(data));
+        return Some(DecodedNftOperation {
+            kind: Mint,
+            standard: NftStandard::Compressed,
+            asset,
+            name: Some(name),
+            collection,
+            from: None,
+            to: account(1),
+            by_token_program: false,
+            unconfirmed: false,
+        });
+    }
+    // leaf owner is the second account, the leaf is identified by the tree and the nonce
+    // that follows root, data hash and creator hash args
+    let (kind, to, tree) = if is("transfer") {
+        (Transfer, account(3), account(4)?)
+    } else if is("burn") {
+        (Burn, None, account(3)?)
+    } else if is("delegate") {
+        (Delegate, account(3), account(4)?)
+    } else {
+        return None;
+    };
+    let nonce = u64::from_le_bytes(data.get(104..112)?.try_into().ok()?);
+    Some(
+        DecodedNftOperation::new(kind, NftStandard::Compressed, find_asset_id(&tree, nonce))
+            .with_from(account(1))
+            .with_to(to),
+    )
+}
+
+/// Token program instructions on a single token of a mint with no decimals,
+/// mints are known from token balances of the accounts
+fn decode_token(
+    accounts: &[Pubkey],
+    data: &[u8],
+    token_accounts: &HashMap<Pubkey, TokenAccountInfo>,
+) -> Option<DecodedNftOperation> {
+    use NftOperationKind::*;
+    let account = |idx: usize| accounts.get(idx).copied();
+    let owner_of = |idx: usize| account(idx).map(|account| token_owner(token_accounts, &account));
+    let (kind, amount, decimals, to) = match TokenInstruction::unpack(data).ok()? {
+        TokenInstruction::Transfer { amount } => (Transfer, amount, None, owner_of(1)),
+        TokenInstruction::TransferChecked { amount, decimals } => {
+            (Transfer, amount, Some(decimals), owner_of(2))
+        }
+        TokenInstruction::MintTo { amount } => (Mint, amount, None, owner_of(1)),
+        TokenInstruction::MintToChecked { amount, decimals } => {
+            (Mint, amount, Some(decimals), owner_of(1))
+        }
+        TokenInstruction::Burn { amount } => (Burn, amount, None, None),
+        TokenInstruction::BurnChecked { amount, decimals } => (Burn, amount, Some(decimals), None),
+        TokenInstruction::Approve { amount } => (Delegate, amount, None, account(1)),
+        TokenInstruction::ApproveChecked { amount, decimals } => {
+            (Delegate, amount, Some(decimals), account(2))
+        }
+        TokenInstruction::Revoke => (Revoke, 1, None, None),
+        TokenInstruction::FreezeAccount => (Lock, 1, None, None),
+        TokenInstruction::ThawAccount => (Unlock, 1, None, None),
+        _ => return None,
+    };
+    // MintTo takes the mint first, other instructions the token account
+    let (token_account, from) = match kind {
+        Mint => (1, None),
+        _ => (0, owner_of(0)),
+    };
+    let info = token_accounts.get(&account(token_account)?)?;
+    if decimals.unwrap_or(info.decimals) != 0 || amount != 1 {
+        return None;
+    }
+    let mut operation = DecodedNftOperation::new(kind, NftStandard::TokenMetadata, info.mint)
+        .with_from(from)
+        .with_to(to)
+        .unconfirmed();
+    operation.by_token_program = true;
+    Some(operation)
+}
+
+/// NFTs of the operations by their asset addresses, Token Metadata NFTs are read
+/// from their metadata accounts and Core assets from their accounts, cNFTs and NFTs
+/// burned by the transaction are fetched from DAS API if the RPC supports it.
+/// Fungible tokens and assets that can't be fetched are skipped
+pub fn get_nft_assets(operations: &[DecodedNftOperation]) -> HashMap<Pubkey, NftAsset> {
+    let mut requested: Vec<(Pubkey, NftStandard)> = Vec::new();
+    for operation in operations {
+        if let Some(asset) = operation.asset {
+            if !requested.iter().any(|(requested, _)| *requested == asset) {
+                requested.push((asset, operation.standard));
+            }
+        }
+    }
+    let mut assets = HashMap::new();
+    let mut not_found = Vec::new();
+    let (compressed, on_chain): (Vec<_>, Vec<_>) = requested
+        .into_iter()
+        .partition(|(_, standard)| *standard == NftStandard::Compressed);
+    for chunk in on_chain.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        let addresses: Vec<Pubkey> = chunk
+            .iter()
+            .map(|(asset, standard)| match standard {
+                NftStandard::Core => *asset,
+                _ => mpl_token_metadata::accounts::Metadata::find_pda(asset).0,
+            })
+            .collect();
+        // assets are nice to have, operations are shown without their names
+        let Ok(accounts) =
+            rpc::send_rate_limited(|rpc_con| rpc_con.get_multiple_accounts(&addresses))
+        else {
+            continue;
+        };
+        for ((asset, standard), account) in chunk.iter().zip(accounts) {
+            let fetched = account.map(|account| match standard {
+                NftStandard::Core => core_asset(&account.owner, &account.data),
+                _ => token_metadata_asset(&account.data),
+            });
+            match fetched {
+                Some(Some(fetched)) => {
+                    assets.insert(*asset, fetched);
+                }
+                // account is closed when the NFT was burned
+                None => not_found.push(*asset),
+                Some(None) => {}
+            }
+        }
+    }
+    for asset in compressed
+        .into_iter()
+        .map(|(asset, _)| asset)
+        .chain(not_found)
+    {
+        if let Some(fetched) = find_das_asset(&asset).and_then(NftAsset::from_das) {
+            assets.insert(asset, fetched);
+        }
+    }
+    assets
+}
+
+fn token_metadata_asset(data: &[u8]) -> Option<NftAsset> {
+    let metadata = mpl_token_metadata::accounts::Metadata::safe_deserialize(data).ok()?;
+    let standard = match metadata.token_standard {
+        Some(TokenStandard::Fungible | TokenStandard::FungibleAsset) => return None,
+        Some(
+            TokenStandard::ProgrammableNonFungible | TokenStandard::ProgrammableNonFungibleEdition,
+        ) => NftStandard::Programmable,
+        // metadata of old NFTs has no token standard
+        _ => NftStandard::TokenMetadata,
+    };
+    Some(NftAsset {
+        name: metadata.name,
+        collection: metadata.collection.map(|collection| collection.key),
+        standard,
+    })
+}
+
+fn core_asset(owner: &Pubkey, data: &[u8]) -> Option<NftAsset> {
+    // Core accounts start with their key, 1 is AssetV1
+    if *owner != mpl_core::ID || data.first() != Some(&1) {
+        return None;
+    }
+    let asset = mpl_core::accounts::BaseAssetV1::from_bytes(data).ok()?;
+    Some(NftAsset {
+        name: asset.name,
+        collection: match asset.update_authority {
+            UpdateAuthority::Collection(collection) => Some(collection),
+            _ => None,
+        },
+        standard: NftStandard::Core,
+    })
+}
@@ -0,0 +1,750 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    process::{self, exit},
+    str::FromStr,
+};
+
+use serde::Serialize;
+
+use super::{
+    balance_change::{token_mints, TokenName},
+    filter::TransactionsFilter,
+    instruction::TOKEN_2022_ID,
+    meta_loaded_addresses,
+    nft::{decode_nft_operations, get_nft_assets},
+    signature::{AccountSignatures, SignatureSummary},
+    TransactionContext, TransactionData,
+};
+use crate::{
+    anchor::idl::fetch_idl,
+    asset::{find_das_asset, get_das_asset_signatures, DAS_MAX_PAGE_SIZE},
+    metaplex::das::AssetSignature,
+    output::{print_error, print_warning, Output, OutputFormat},
+    page::{Page, PageStream},
+    rpc,
+};
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind},
+    rpc_client::GetConfirmedSignaturesForAddress2Config,
+    rpc_config::RpcTransactionConfig,
+    rpc_response::RpcConfirmedTransactionStatusWithSignature,
+};
+use solana_sdk::{
+    address_lookup_table::state::AddressLookupTable,
+    commitment_config::CommitmentConfig,
+    message::{v0::LoadedAddresses, VersionedMessage},
+    pubkey::Pubkey,
+    signature::Signature,
+};
+use solana_transaction_status::{EncodedConfirmedTransactionWithStatusMeta, UiTransactionEncoding};
+use spl_token_2022::extension::{BaseStateWithExtensions, StateWithExtensions};
+use spl_token_metadata_interface::state::TokenMetadata;
+
+/// `getSignaturesForAddress` does not return more than 1000 signatures per request
+pub const MAX_SIGNATURES_PAGE_SIZE: usize = 1000;
+
+/// Range and number of account transactions to list, newest first
+#[derive(Debug)]
+pub struct TransactionsPagination {
+    /// ignored when `all` is set
+    pub limit: usize,
+    /// start listing from transactions older than this signature
+    pub before: Option<String>,
+    /// stop listing at this signature
+    pub until: Option<String>,
+    /// list the whole history
+    pub all: bool,
+}
+
+impl TransactionsPagination {
+    /// first of `before` and `until` cursors that is not a valid signature
+    pub fn invalid_cursor(&self) -> Option<&String> {
+        [&self.before, &self.until]
+            .into_iter()
+            .flatten()
+            .find(|cursor| Signature::from_str(cursor).is_err())
+    }
+
+    /// max number of transactions to list
+    pub fn max_count(&self) -> usize {
+        match self.all {
+            true => usize::MAX,
+            false => self.limit,
+        }
+    }
+}
+
+/// Transaction of the listing that failed to be fetched or decoded
+#[derive(Debug, Serialize)]
+pub struct TransactionFetchError {
+    pub signature: String,
+    pub error: String,
+}
+
+impl TransactionFetchError {
+    pub fn new(signature: &str, err: ClientError) -> Self {
+        TransactionFetchError {
+            signature: signature.to_string(),
+            error: err.to_string(),
+        }
+    }
+}
+
+impl Output for TransactionFetchError {
+    fn struct_name(&self) -> String {
+        String::from("TransactionFetchError")
+    }
+
+    fn to_raw_struct(&self) -> String {
+        format!("{:#?}", self)
+    }
+
+    fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    fn is_repeatable(&self) -> bool {
+        true
+    }
+}
+
+pub fn read_tx(sig_hash: &str, output_format: OutputFormat) {
+    let sig = match Signature::from_str(sig_hash) {
+        Ok(sig) => sig,
+        Err(_) => {
+            print_warning(format!("signature {:?} is not a valid", sig_hash).as_str());
+            process::exit(1);
+        }
+    };
+    let data = match get_tx_data(&sig) {
+        Ok(data) => data,
+        Err(err) => {
+            print_error(err);
+            process::exit(1);
+        }
+    };
+    let mut page = Page::new(output_format);
+    add_tx_data_to_page(&data, &mut page);
+    page.display();
+}
+
+/// fetch transaction by signature with addresses it loaded from lookup tables
+pub fn get_tx_data(sig: &Signature) -> Result<TransactionData, ClientError> {
+    let tx = get_tx(sig)?;
+    let transaction = tx.transaction.transaction.decode().ok_or_else(|| {
+        ClientError::from(ClientErrorKind::Custom(format!(
+            "can't decode transaction {}",
+            sig
+        )))
+    })?;
+    let meta = tx.transaction.meta;
+    let loaded_addresses = match meta.as_ref().and_then(meta_loaded_addresses) {
+        Some(loaded_addresses) => Some(loaded_addresses),
+        // meta of some RPCs lacks loaded addresses, resolve them from lookup tables
+        None => resolve_lookup_tables(&transaction.message)?,
+    };
+    Ok(TransactionData {
+        transaction,
+        meta,
+        loaded_addresses,
+        slot: Some(tx.slot),
+        block_time: tx.block_time,
+    })
+}
+
+/// Transaction of a listing with its context when it is shown in full
+struct ListedTx {
+    data: TransactionData,
+    context: TransactionContext,
+}
+
+impl ListedTx {
+    fn fetch(signature: &str, with_context: bool) -> Result<Self, ClientError> {
+        let sig = Signature::from_str(signature)
+            .map_err(|err| ClientError::from(ClientErrorKind::Custom(err.to_string())))?;
+        let data = get_tx_data(&sig)?;
+        let context = match with_context {
+            true => get_tx_context(&data)?,
+            false => TransactionContext::default(),
+        };
+        Ok(ListedTx { data, context })
+    }
+}
+
+/// Fetch transactions concurrently, up to `limit` of them that match the filter, or failed
+/// to be fetched, are passed to `on_match` in order with their indexes in `signatures`
+/// as soon as they are fetched. Returns the number of matching transactions
+fn fetch_matching_txs(
+    signatures: &[&str],
+    limit: usize,
+    address: &Pubkey,
+    filter: &TransactionsFilter,
+    with_context: bool,
+    concurrency: usize,
+    mut on_match: impl FnMut(usize, Result<ListedTx, ClientError>),
+) -> usize {
+    let mut matching = 0;
+    if limit == 0 {
+        return matching;
+    }
+    rpc::fetch_concurrently(
+        signatures,
+        concurrency,
+        |signature| ListedTx::fetch(signature, with_context),
+        |idx, tx| {
+            if let Ok(listed_tx) = &tx {
+                if !filter.matches_transaction(&listed_tx.data, address) {
+                    return true;
+                }
+            }
+            matching += 1;
+            on_match(idx, tx);
+            matching < limit
+        },
+    );
+    matching
+}
+
+/// Listed account transactions, rows of the signature table or pages of transactions in full,
+/// pages and NDJSON rows are displayed as soon as they are added
+struct TxListing {
+    details: bool,
+    stream: PageStream,
+    summaries: Vec<SignatureSummary>,
+    failures: Vec<TransactionFetchError>,
+}
+
+impl TxListing {
+    fn new(details: bool, output_format: OutputFormat) -> Self {
+        TxListing {
+            details,
+            stream: PageStream::new(output_format),
+            summaries: Vec::new(),
+            failures: Vec::new(),
+        }
+    }
+
+    /// NDJSON has no table, each row is a line on its own
+    fn is_row_streamed(&self) -> bool {
+        matches!(self.stream.format(), OutputFormat::AsNdJson)
+    }
+
+    fn add(&mut self, status: &RpcConfirmedTransactionStatusWithSignature, tx: Option<ListedTx>) {
+        match (tx, self.details) {
+            (Some(tx), true) => {
+                let mut page = self.stream.page();
+                tx.data.add_to_page(&mut page, &tx.context);
+                self.stream.push(page);
+            }
+            _ if self.is_row_streamed() => {
+                let mut page = self.stream.page();
+                page.add(SignatureSummary::from(status));
+                self.stream.push(page);
+            }
+            _ => self.summaries.push(SignatureSummary::from(status)),
+        }
+    }
+
+    fn add_failure(&mut self, failure: TransactionFetchError) {
+        if self.details || self.is_row_streamed() {
+            let mut page = self.stream.page();
+            page.add(failure);
+            self.stream.push(page);
+        } else {
+            self.failures.push(failure);
+        }
+    }
+
+    fn finish(self) {
+        if !self.details && !self.is_row_streamed() {
+            let mut page = self.stream.page();
+            page.add(AccountSignatures {
+                signatures: self.summaries,
+            });
+            self.failures
+                .into_iter()
+                .for_each(|failure| page.add(failure));
+            page.display();
+            return;
+        }
+        self.stream.finish();
+    }
+}
+
+/// add transaction parts to the page with their context fetched over RPC
+pub fn add_tx_data_to_page(data: &TransactionData, page: &mut Page) {
+    match get_tx_context(data) {
+        Ok(context) => data.add_to_page(page, &context),
+        Err(err) => {
+            print_error(err);
+            process::exit(1);
+        }
+    }
+}
+
+/// Mint names of token balance changes, NFTs the transaction operates on
+/// and IDLs of programs that emitted data in logs
+pub fn get_tx_context(data: &TransactionData) -> Result<TransactionContext, ClientError> {
+    let token_names = match &data.meta {
+        Some(meta) => get_token_names(&token_mints(meta)),
+        None => HashMap::new(),
+    };
+    let nft_operations = decode_nft_operations(data, &data.account_keys());
+    let data_programs = data.logs().map(|logs| logs.data_programs());
+    let mut idls = HashMap::new();
+    for program_id in data_programs.unwrap_or_default() {
+        // programs without IDL account are skipped, their data stays undecoded
+        if let Some(idl) = fetch_idl(&program_id)? {
+            idls.insert(program_id, idl);
+        }
+    }
+    Ok(TransactionContext {
+        token_names,
+        nft_assets: get_nft_assets(&nft_operations),
+        idls,
+    })
+}
+
+/// Addresses a v0 message loads from lookup tables, fetched from the current state of the tables,
+/// `None` for legacy messages and when a table is closed or no longer has an address
+/// the message refers to, accounts loaded from the tables are unresolved then
+pub fn resolve_lookup_tables(
+    message: &VersionedMessage,
+) -> Result<Option<LoadedAddresses>, ClientError> {
+    let Some(lookups) = message.address_table_lookups() else {
+        return Ok(None);
+    };
+    let table_addresses: Vec<Pubkey> = lookups.iter().map(|lookup| lookup.account_key).collect();
+    let tables = rpc::send_rate_limited(|rpc_con| rpc_con.get_multiple_accounts(&table_addresses))?;
+
+    let mut loaded_addresses = LoadedAddresses::default();
+    for (lookup, table) in lookups.iter().zip(tables) {
+        let Some(table) = table else {
+            return Ok(None);
+        };
+        let Ok(table) = AddressLookupTable::deserialize(&table.data) else {
+            return Ok(None);
+        };
+        let lookup_addresses = |indexes: &[u8]| -> Option<Vec<Pubkey>> {
+            indexes
+                .iter()
+                .map(|index| table.addresses.get(*index as usize).copied())
+                .collect()
+        };
+        let (Some(writable), Some(readonly)) = (
+            lookup_addresses(&lookup.writable_indexes),
+            lookup_addresses(&lookup.readonly_indexes),
+        ) else {
+            return Ok(None);
+        };
+        loaded_addresses.writable.extend(writable);
+        loaded_addresses.readonly.extend(readonly);
+    }
+    Ok(Some(loaded_addresses))
+}
+
+/// Token Metadata names of the mints, Token-2022 mints without Token Metadata account
+/// are named by their metadata extension, mints without metadata are skipped
+pub fn get_token_names(mints: &[Pubkey]) -> HashMap<Pubkey, TokenName> {
+    if mints.is_empty() {
+        return HashMap::new();
+    }
+    let metadata_addresses: Vec<Pubkey> = mints
+        .iter()
+        .map(|mint| mpl_token_metadata::accounts::Metadata::find_pda(mint).0)
+        .collect();
+    let accounts = match rpc::send_rate_limited(|rpc_con| {
+        rpc_con.get_multiple_accounts(&metadata_addresses)
+    }) {
+        Ok(accounts) => accounts,
+        // names are nice to have, balance changes are shown without them
+        Err(_) => return HashMap::new(),
+    };
+    let mut names: HashMap<Pubkey, TokenName> = mints
+        .iter()
+        .zip(accounts)
+        .filter_map(|(mint, account)| {
+            let metadata =
+                mpl_token_metadata::accounts::Metadata::safe_deserialize(&account?.data).ok()?;
+            Some((
+                *mint,
+                TokenName {
+                    name: metadata.name.trim_end_matches('\0').to_string(),
+                    symbol: metadata.symbol.trim_end_matches('\0').to_string(),
+                },
+            ))
+        })
+        .collect();
+    let unnamed: Vec<Pubkey> = mints
+        .iter()
+        .filter(|mint| !names.contains_key(mint))
+        .copied()
+        .collect();
+    if unnamed.is_empty() {
+        return names;
+    }
+    let Ok(mint_accounts) =
+        rpc::send_rate_limited(|rpc_con| rpc_con.get_multiple_accounts(&unnamed))
+    else {
+        return names;
+    };
+    for (mint, account) in unnamed.into_iter().zip(mint_accounts) {
+        let name = account
+            .filter(|account| account.owner == TOKEN_2022_ID)
+            .and_then(|account| token_2022_name(&account.data));
+        if let Some(name) = name {
+            names.insert(mint, name);
+        }
+    }
+    names
+}
+
+/// name from the metadata extension of a Token-2022 mint, it is stored in the mint account
+/// when the metadata pointer points to the mint itself
+fn token_2022_name(data: &[u8]) -> Option<TokenName> {
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(data).ok()?;
+    let metadata = mint.get_variable_len_extension::<TokenMetadata>().ok()?;
+    Some(TokenName {
+        name: metadata.name,
+        symbol: metadata.symbol,
+    })
+}
+
+fn get_tx(sig: &Signature) -> Result<EncodedConfirmedTransactionWithStatusMeta, ClientError> {
+    let conf = RpcTransactionConfig {
+        // raw transaction is decoded locally, see TransactionData
+        encoding: Some(UiTransactionEncoding::Base64),
+        commitment: Some(CommitmentConfig::confirmed()),
+        max_supported_transaction_version: Some(0),
+    };
+    rpc::send_rate_limited(|rpc_con| rpc_con.get_transaction_with_config(sig, conf))
+}
+
+/// List account transactions as a signature table, `details` fetches and shows
+/// each transaction in full. Signatures are fetched page by page and filtered on the way,
+/// transactions are fetched only when needed for filters or details, `concurrency` of them at once
+pub fn list_account_txs(
+    address: &str,
+    pagination: &TransactionsPagination,
+    filter: &TransactionsFilter,
+    details: bool,
+    concurrency: usize,
+    output_format: OutputFormat,
+) {
+    let acc_pubkey = match Pubkey::from_str(address) {
+        Ok(pubkey) => pubkey,
+        Err(_) => {
+            print_warning(
+                format!("address {:?} is not a valid solana public key", address).as_str(),
+            );
+            exit(1);
+        }
+    };
+    if let Some(cursor) = pagination.invalid_cursor() {
+        print_warning(format!("signature {:?} is not a valid", cursor).as_str());
+        exit(1);
+    }
+    let rpc_con = rpc::init_connection();
+    let account = rpc_con.get_account_with_commitment(&acc_pubkey, rpc_con.commitment());
+    // solana account not found, it can be a compressed digital asset (cNFT), addresses
+    // without an account are listed as usual when the RPC does not support DAS API
+    if account.is_ok_and(|account| account.value.is_none()) {
+        if let Some(asset) = find_das_asset(&acc_pubkey) {
+            if asset.compression.compressed {
+                list_compressed_asset_txs(
+                    &acc_pubkey,
+                    pagination,
+                    filter,
+                    details,
+                    concurrency,
+                    output_format,
+                );
+                return;
+            }
+        }
+    }
+
+    let limit = pagination.max_count();
+    let mut signature_pages = SignaturePages::new(acc_pubkey, pagination);
+    let mut listing = TxListing::new(details, output_format);
+    let mut listed = 0;
+    while listed < limit {
+        // without filters every signature is listed, don't fetch more than needed
+        let page_size = match filter.is_empty() {
+            true => limit - listed,
+            false => MAX_SIGNATURES_PAGE_SIZE,
+        };
+        let statuses = match signature_pages.next(page_size) {
+            Ok(statuses) => statuses,
+            Err(err) => {
+                print_error(err);
+                exit(1);
+            }
+        };
+        if statuses.is_empty() {
+            break;
+        }
+        let mut is_history_over = false;
+        let mut candidates = Vec::new();
+        for status in statuses {
+            if filter.is_before_range(status.block_time) {
+                is_history_over = true;
+                break;
+            }
+            if filter.matches_status(&status) {
+                candidates.push(status);
+            }
+        }
+
+        if !details && !filter.needs_transaction() {
+            for status in candidates.iter().take(limit - listed) {
+                listing.add(status, None);
+                listed += 1;
+            }
+        } else {
+            let signatures: Vec<&str> = candidates
+                .iter()
+                .map(|status| status.signature.as_str())
+                .collect();
+            listed += fetch_matching_txs(
+                &signatures,
+                limit - listed,
+                &acc_pubkey,
+                filter,
+                details,
+                concurrency,
+                |idx, tx| {
+                    let status = &candidates[idx];
+                    match tx {
+                        Ok(tx) => listing.add(status, Some(tx)),
+                        Err(err) => {
+                            listing.add_failure(TransactionFetchError::new(&status.signature, err))
+                        }
+                    }
+                },
+            );
+        }
+        if is_history_over {
+            break;
+        }
+    }
+    listing.finish();
+}
+
+/// Signatures of account transactions from the newest, fetched page by page
+/// with the oldest signature of a page as the `before` cursor of the next one
+pub struct SignaturePages {
+    pubkey: Pubkey,
+    before: Option<Signature>,
+    until: Option<Signature>,
+    is_done: bool,
+}
+
+impl SignaturePages {
+    pub fn new(pubkey: Pubkey, pagination: &TransactionsPagination) -> Self {
+        let parse_cursor = |cursor: &Option<String>| {
+            cursor
+                .as_ref()
+                .and_then(|cursor| Signature::from_str(cursor).ok())
+        };
+        SignaturePages {
+            pubkey,
+            before: parse_cursor(&pagination.before),
+            until: parse_cursor(&pagination.until),
+            is_done: false,
+        }
+    }
+
+    /// next page of at most `limit` signatures, empty when the history is over
+    pub fn next(
+        &mut self,
+        limit: usize,
+    ) -> Result<Vec<RpcConfirmedTransactionStatusWithSignature>, ClientError> {
+        let limit = limit.min(MAX_SIGNATURES_PAGE_SIZE);
+        if self.is_done || limit == 0 {
+            return Ok(Vec::new());
+        }
+        let mut statuses = rpc::send_rate_limited(|rpc_con| {
+            let conf = GetConfirmedSignaturesForAddress2Config {
+                before: self.before,
+                until: self.until,
+                limit: Some(limit),
+                commitment: Some(CommitmentConfig::confirmed()),
+            };
+            rpc_con.get_signatures_for_address_with_config(&self.pubkey, conf)
+        })?;
+        statuses.truncate(limit);
+        self.before = statuses
+            .last()
+            .and_then(|status| Signature::from_str(&status.signature).ok());
+        self.is_done = statuses.len() < limit || self.before.is_none();
+        Ok(statuses)
+    }
+}
+
+fn list_compressed_asset_txs(
+    asset_pubkey: &Pubkey,
+    pagination: &TransactionsPagination,
+    filter: &TransactionsFilter,
+    details: bool,
+    concurrency: usize,
+    output_format: OutputFormat,
+) {
+    // compressed assets are not accounts, they have no SOL balance to change
+    if filter.min_sol_change.is_some() {
+        print_warning("--min-sol-change can't be used with compressed NFT history");
+        exit(1);
+    }
+    let limit = pagination.max_count();
+    let mut stream = PageStream::new(output_format.clone());
+    // NDJSON has no summary page, each asset signature is a line on its own
+    let mut summary_page = match (details, &output_format) {
+        (false, OutputFormat::AsNdJson) | (true, _) => None,
+        (false, _) => Some(Page::new(output_format.clone())),
+    };
+    let mut add = |page: Page| match &mut summary_page {
+        Some(summary_page) => summary_page.extend(page),
+        None => stream.push(page),
+    };
+    // DAS pages are numbered, page size can't change between pages. Without filters and
+    // cursors every signature is listed, don't fetch more than needed
+    let page_size = match filter.is_empty() && pagination.before.is_none() {
+        true => limit.clamp(1, DAS_MAX_PAGE_SIZE),
+        false => DAS_MAX_PAGE_SIZE,
+    };
+    let mut is_before_found = pagination.before.is_none();
+    let mut page_num = 1;
+    let mut listed = 0;
+    while listed < limit {
+        let page = match get_das_asset_signatures(asset_pubkey, page_num, page_size) {
+            Ok(page) => page,
+            Err(err) => {
+                print_error(err);
+                exit(1);
+            }
+        };
+        let mut is_history_over = page.len() < page_size;
+        // DAS has no cursors, they are applied to the fetched signatures here
+        let mut candidates = Vec::new();
+        for asset_sig in page {
+            if !is_before_found {
+                is_before_found = pagination.before.as_ref() == Some(&asset_sig.signature);
+                continue;
+            }
+            if pagination.until.as_ref() == Some(&asset_sig.signature) {
+                is_history_over = true;
+                break;
+            }
+            candidates.push(asset_sig);
+        }
+
+        // DAS lists only signatures and operations, filters are applied to fetched transactions
+        if !details && filter.is_empty() {
+            for asset_sig in candidates.into_iter().take(limit - listed) {
+                let mut page = Page::new(output_format.clone());
+                page.add(asset_sig);
+                add(page);
+                listed += 1;
+            }
+        } else {
+            let mut candidates: Vec<Option<AssetSignature>> =
+                candidates.into_iter().map(Some).collect();
+            let signature_strs: Vec<String> = candidates
+                .iter()
+                .flatten()
+                .map(|asset_sig| asset_sig.signature.clone())
+                .collect();
+            let signature_refs: Vec<&str> = signature_strs.iter().map(String::as_str).collect();
+            listed += fetch_matching_txs(
+                &signature_refs,
+                limit - listed,
+                asset_pubkey,
+                filter,
+                details,
+                concurrency,
+                |idx, tx| {
+                    let asset_sig = candidates[idx].take().unwrap();
+                    let signature = asset_sig.signature.clone();
+                    let mut page = Page::new(output_format.clone());
+                    page.add(asset_sig);
+                    match tx {
+                        Ok(tx) if details => tx.data.add_to_page(&mut page, &tx.context),
+                        Ok(_) => {}
+                        Err(err) => page.add(TransactionFetchError::new(&signature, err)),
+                    }
+                    add(page);
+                },
+            );
+        }
+        if is_history_over {
+            break;
+        }
+        page_num += 1;
+    }
+    // nothing is listed when the cursor is not a signature of the asset
+    if !is_before_found {
+        print_warning(
+            format!(
+                "signature {:?} is not in the history of the asset",
+                pagination.before.as_deref().unwrap_or_default()
+            )
+            .as_str(),
+        );
+        exit(1);
+    }
+    match summary_page {
+        Some(summary_page) => summary_page.display(),
+        None => stream.finish(),
+    }
+}
+
+/// Signatures of transactions of several accounts from the newest, merged by slot
+/// without duplicates of transactions shared by the accounts
+pub struct MergedSignaturePages {
+    sources: Vec<(
+        SignaturePages,
+        VecDeque<RpcConfirmedTransactionStatusWithSignature>,
+    )>,
+    seen: HashSet<String>,
+}
+
+impl MergedSignaturePages {
+    pub fn new(pubkeys: &[Pubkey], pagination: &TransactionsPagination) -> Self {
+        MergedSignaturePages {
+            sources: pubkeys
+                .iter()
+                .map(|pubkey| (SignaturePages::new(*pubkey, pagination), VecDeque::new()))
+                .collect(),
+            seen: HashSet::new(),
+        }
+    }
+
+    /// next page of at most `limit` signatures (and at most a page of signatures
+    /// of a single account), empty when histories of all accounts are over
+    pub fn next(
+        &mut self,
+        limit: usize,
+    ) -> Result<Vec<RpcConfirmedTransactionStatusWithSignature>, ClientError> {
+        let limit = limit.min(MAX_SIGNATURES_PAGE_SIZE);
+        let mut statuses = Vec::new();
+        while statuses.len() < limit {
+            for (pages, buffer) in &mut self.sources {
+                if buffer.is_empty() {
+                    buffer.extend(pages.next(limit)?);
+                }
+            }
+            let newest = self
+                .sources
+                .iter_mut()
+                .filter_map(|(_, buffer)| Some((buffer.front()?.slot, buffer)))
+                .max_by_key(|(slot, _)| *slot);
+            let Some(status) = newest.and_then(|(_, buffer)| buffer.pop_front()) else {
+                break;
+            };
+            if self.seen.insert(status.signature.clone()) {
+                statuses.push(status);
+            }
+        }
+        Ok(statuses)
+    }
+}
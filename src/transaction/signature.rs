@@ -0,0 +1,95 @@
+use serde::Serialize;
+use solana_client::rpc_response::RpcConfirmedTransactionStatusWithSignature;
+
+use crate::{
+    output::Output,
+    pretty::{table::format_table, time::PrettyTimestamp},
+};
+
+/// Account transaction as listed by `getSignaturesForAddress`, without fetching the transaction
+#[derive(Debug, Serialize)]
+pub struct SignatureSummary {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: Option<PrettyTimestamp>,
+    /// "Success" or the transaction error
+    pub status: String,
+    pub memo: Option<String>,
+    pub confirmation_status: Option<String>,
+}
+
+impl From<&RpcConfirmedTransactionStatusWithSignature> for SignatureSummary {
+    fn from(status: &RpcConfirmedTransactionStatusWithSignature) -> Self {
+        SignatureSummary {
+            signature: status.signature.clone(),
+            slot: status.slot,
+            block_time: status.block_time.map(PrettyTimestamp::from),
+            status: match &status.err {
+                Some(err) => err.to_string(),
+                None => String::from("Success"),
+            },
+            memo: status.memo.clone(),
+            confirmation_status: status
+                .confirmation_status
+                .as_ref()
+                .map(|confirmation| format!("{:?}", confirmation)),
+        }
+    }
+}
+
+impl Output for SignatureSummary {
+    fn struct_name(&self) -> String {
+        String::from("SignatureSummary")
+    }
+
+    fn to_raw_struct(&self) -> String {
+        format!("{:#?}", self)
+    }
+
+    fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+/// Account transactions, one table row per signature in raw output
+#[derive(Debug, Serialize)]
+pub struct AccountSignatures {
+    pub signatures: Vec<SignatureSummary>,
+}
+
+impl Output for AccountSignatures {
+    fn struct_name(&self) -> String {
+        String::from("AccountSignatures")
+    }
+
+    fn to_raw_struct(&self) -> String {
+        let header = [
+            "SIGNATURE",
+            "SLOT",
+            "BLOCK TIME",
+            "CONFIRMATION",
+            "STATUS",
+            "MEMO",
+        ];
+        let rows: Vec<Vec<String>> = std::iter::once(header.map(String::from).to_vec())
+            .chain(self.signatures.iter().map(|summary| {
+                vec![
+                    summary.signature.clone(),
+                    summary.slot.to_string(),
+                    summary
+                        .block_time
+                        .map(|time| time.to_string())
+                        .unwrap_or_default(),
+                    summary.confirmation_status.clone().unwrap_or_default(),
+                    summary.status.clone(),
+                    summary.memo.clone().unwrap_or_default(),
+                ]
+            }))
+            .collect();
+        format!("{}\n{}", self.struct_name(), format_table(&rows))
+    }
+
+    fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
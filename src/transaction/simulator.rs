@@ -0,0 +1,364 @@
+use std::{collections::HashMap, process::exit};
+
+use serde::Serialize;
+use solana_account_decoder_client_types::{token::UiTokenAmount, UiAccountEncoding};
+use solana_client::{
+    client_error::ClientError,
+    rpc_config::{
+        RpcAccountInfoConfig, RpcSimulateTransactionAccountsConfig, RpcSimulateTransactionConfig,
+    },
+};
+use solana_sdk::{
+    account::Account as SolanaAccount, commitment_config::CommitmentConfig, pubkey::Pubkey,
+};
+use solana_transaction_status::{
+    option_serializer::OptionSerializer, UiTransactionEncoding, UiTransactionStatusMeta,
+    UiTransactionTokenBalance,
+};
+use spl_token_2022::{
+    extension::StateWithExtensions,
+    state::{Account as TokenAccount, Mint},
+};
+
+use super::{
+    decoder::parse_transaction,
+    fee::FeeBreakdown,
+    instruction::TOKEN_2022_ID,
+    reader::{add_tx_data_to_page, resolve_lookup_tables},
+    TransactionData,
+};
+use crate::{
+    output::{print_error, print_warning, Output, OutputFormat},
+    page::Page,
+    pretty::{amount::PrettyAmount, public_key::PrettyPublicKey},
+    rpc,
+};
+
+/// max number of accounts `getMultipleAccounts` returns at once
+const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+#[derive(Debug, Serialize)]
+pub struct AccountState {
+    pub lamports: u64,
+    pub owner: PrettyPublicKey,
+    pub data_len: usize,
+}
+
+impl From<&SolanaAccount> for AccountState {
+    fn from(account: &SolanaAccount) -> Self {
+        AccountState {
+            lamports: account.lamports,
+            owner: account.owner.into(),
+            data_len: account.data.len(),
+        }
+    }
+}
+
+/// State of a writable account before and after the simulation, `None` if account doesn't exist
+#[derive(Debug, Serialize)]
+pub struct SimulatedAccount {
+    pub pubkey: PrettyPublicKey,
+    pub pre: Option<AccountState>,
+    pub post: Option<AccountState>,
+    pub data_changed: bool,
+}
+
+/// States of writable accounts, pre-states are read at `pre_state_slot`,
+/// the simulation runs at this slot or a later one shown in the transaction header
+#[derive(Debug, Serialize)]
+pub struct SimulatedAccounts {
+    pub pre_state_slot: u64,
+    pub accounts: Vec<SimulatedAccount>,
+}
+
+impl Output for SimulatedAccounts {
+    fn struct_name(&self) -> String {
+        String::from("SimulatedAccounts")
+    }
+
+    fn to_raw_struct(&self) -> String {
+        format!("{:#?}", self)
+    }
+
+    fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+/// Data returned by the last program that set return data, base64 encoded
+#[derive(Debug, Serialize)]
+pub struct ReturnData {
+    pub program: PrettyPublicKey,
+    pub data: String,
+}
+
+impl Output for ReturnData {
+    fn struct_name(&self) -> String {
+        String::from("ReturnData")
+    }
+
+    fn to_raw_struct(&self) -> String {
+        format!("{:#?}", self)
+    }
+
+    fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+/// Accounts and the slot they are read at, every chunk is read at the slot of
+/// the previous one or later, so the accounts are not older than the returned slot
+fn get_accounts(
+    pubkeys: &[Pubkey],
+    min_context_slot: Option<u64>,
+) -> Result<(Vec<Option<SolanaAccount>>, u64), ClientError> {
+    let rpc_con = rpc::init_connection();
+    let mut accounts = Vec::with_capacity(pubkeys.len());
+    let mut slot = min_context_slot;
+    for chunk in pubkeys.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        let conf = RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            data_slice: None,
+            commitment: Some(CommitmentConfig::confirmed()),
+            min_context_slot: slot,
+        };
+        let response = rpc_con.get_multiple_accounts_with_config(chunk, conf)?;
+        slot = Some(slot.unwrap_or_default().max(response.context.slot));
+        accounts.extend(response.value);
+    }
+    Ok((accounts, slot.unwrap_or_default()))
+}
+
+/// mint, owner and amount of an SPL Token or Token-2022 account
+fn token_account(account: &SolanaAccount) -> Option<TokenAccount> {
+    if account.owner != spl_token::ID && account.owner != TOKEN_2022_ID {
+        return None;
+    }
+    StateWithExtensions::<TokenAccount>::unpack(&account.data)
+        .ok()
+        .map(|state| state.base)
+}
+
+/// Token balances of the token accounts like transaction meta has them,
+/// `accounts` are indexed as the transaction account keys
+fn token_balances(
+    accounts: &[Option<SolanaAccount>],
+    mint_decimals: &HashMap<Pubkey, u8>,
+) -> Vec<UiTransactionTokenBalance> {
+    accounts
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, account)| {
+            let account = account.as_ref()?;
+            let token_account = token_account(account)?;
+            let decimals = *mint_decimals.get(&token_account.mint)?;
+            let ui_amount = PrettyAmount::new(token_account.amount as i128, decimals);
+            Some(UiTransactionTokenBalance {
+                account_index: idx as u8,
+                mint: token_account.mint.to_string(),
+                ui_token_amount: UiTokenAmount {
+                    ui_amount: Some(token_account.amount as f64 / 10f64.powi(decimals as i32)),
+                    decimals,
+                    amount: token_account.amount.to_string(),
+                    ui_amount_string: ui_amount.to_string(),
+                },
+                owner: OptionSerializer::Some(token_account.owner.to_string()),
+                program_id: OptionSerializer::Some(account.owner.to_string()),
+            })
+        })
+        .collect()
+}
+
+/// decimals of the mints of the token accounts, mints that are not among `known`
+/// accounts are read at `slot` or later
+fn get_mint_decimals(
+    token_accounts: &[TokenAccount],
+    known: &[(Pubkey, Option<SolanaAccount>)],
+    slot: u64,
+) -> HashMap<Pubkey, u8> {
+    let decimals = |account: &SolanaAccount| {
+        StateWithExtensions::<Mint>::unpack(&account.data)
+            .ok()
+            .map(|state| state.base.decimals)
+    };
+    let mut mints: Vec<Pubkey> = token_accounts.iter().map(|account| account.mint).collect();
+    mints.sort();
+    mints.dedup();
+    let mut mint_decimals = HashMap::new();
+    let mut unknown = Vec::new();
+    for mint in mints {
+        match known.iter().find(|(pubkey, _)| *pubkey == mint) {
+            Some((_, account)) => {
+                if let Some(decimals) = account.as_ref().and_then(decimals) {
+                    mint_decimals.insert(mint, decimals);
+                }
+            }
+            None => unknown.push(mint),
+        }
+    }
+    // token balances are nice to have, balance changes are shown without them
+    if let Ok((accounts, _)) = get_accounts(&unknown, Some(slot)) {
+        for (mint, account) in unknown.into_iter().zip(accounts) {
+            if let Some(decimals) = account.as_ref().and_then(decimals) {
+                mint_decimals.insert(mint, decimals);
+            }
+        }
+    }
+    mint_decimals
+}
+
+/// Simulate serialized transaction without signature verification and with the latest blockhash,
+/// shows the transaction page with simulated effects and states of writable accounts.
+/// The simulation runs no earlier than the slot the pre-states are read at
+pub fn simulate_tx(input: &str, output_format: OutputFormat) {
+    let transaction = match parse_transaction(input) {
+        Some(transaction) => transaction,
+        None => {
+            print_warning("input is not a base64 or base58 encoded transaction or a file with it");
+            exit(1);
+        }
+    };
+    let loaded_addresses = match resolve_lookup_tables(&transaction.message) {
+        Ok(loaded_addresses) => loaded_addresses,
+        Err(err) => {
+            print_error(err);
+            exit(1);
+        }
+    };
+    let mut data = TransactionData {
+        transaction,
+        meta: None,
+        loaded_addresses,
+        slot: None,
+        block_time: None,
+    };
+    let account_keys = data.account_keys();
+    let writable_keys: Vec<Pubkey> = account_keys
+        .iter()
+        .enumerate()
+        .filter(|(idx, _)| data.transaction.message.is_maybe_writable(*idx, None))
+        .map(|(_, pubkey)| *pubkey)
+        .collect();
+
+    let (pre_accounts, pre_state_slot) = match get_accounts(&account_keys, None) {
+        Ok(accounts) => accounts,
+        Err(err) => {
+            print_error(err);
+            exit(1);
+        }
+    };
+    let rpc_con = rpc::init_connection();
+    let conf = RpcSimulateTransactionConfig {
+        sig_verify: false,
+        replace_recent_blockhash: true,
+        commitment: Some(CommitmentConfig::confirmed()),
+        encoding: Some(UiTransactionEncoding::Base64),
+        accounts: Some(RpcSimulateTransactionAccountsConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            addresses: writable_keys.iter().map(|key| key.to_string()).collect(),
+        }),
+        min_context_slot: Some(pre_state_slot),
+        inner_instructions: true,
+    };
+    let simulation = match rpc_con.simulate_transaction_with_config(&data.transaction, conf) {
+        Ok(simulation) => simulation,
+        Err(err) => {
+            print_error(err);
+            exit(1);
+        }
+    };
+    let result = simulation.value;
+    let post_writable_accounts: Vec<Option<SolanaAccount>> = result
+        .accounts
+        .unwrap_or_default()
+        .into_iter()
+        .map(|account| account.and_then(|account| account.decode()))
+        .collect();
+    let post_account = |pubkey: &Pubkey| {
+        writable_keys
+            .iter()
+            .position(|key| key == pubkey)
+            .and_then(|pos| post_writable_accounts.get(pos).cloned().flatten())
+    };
+
+    let post_accounts: Vec<Option<SolanaAccount>> = account_keys
+        .iter()
+        .zip(pre_accounts.iter())
+        .map(|(pubkey, pre)| match writable_keys.contains(pubkey) {
+            true => post_account(pubkey),
+            false => pre.clone(),
+        })
+        .collect();
+    let lamports = |account: &Option<SolanaAccount>| account.as_ref().map_or(0, |a| a.lamports);
+    let pre_balances: Vec<u64> = pre_accounts.iter().map(lamports).collect();
+    let post_balances: Vec<u64> = post_accounts.iter().map(lamports).collect();
+    let token_accounts: Vec<TokenAccount> = pre_accounts
+        .iter()
+        .chain(post_accounts.iter())
+        .flatten()
+        .filter_map(token_account)
+        .collect();
+    let known: Vec<(Pubkey, Option<SolanaAccount>)> = account_keys
+        .iter()
+        .copied()
+        .zip(pre_accounts.iter().cloned())
+        .collect();
+    let mint_decimals = get_mint_decimals(&token_accounts, &known, pre_state_slot);
+    let fee = {
+        let breakdown = FeeBreakdown::new(&data, &account_keys);
+        breakdown.base_fee + breakdown.priority_fee
+    };
+    let return_data = result.return_data.clone();
+    data.meta = Some(UiTransactionStatusMeta {
+        err: result.err.clone(),
+        status: match result.err {
+            Some(err) => Err(err),
+            None => Ok(()),
+        },
+        fee,
+        pre_balances,
+        post_balances,
+        inner_instructions: result.inner_instructions.into(),
+        log_messages: result.logs.into(),
+        pre_token_balances: OptionSerializer::Some(token_balances(&pre_accounts, &mint_decimals)),
+        post_token_balances: OptionSerializer::Some(token_balances(&post_accounts, &mint_decimals)),
+        rewards: OptionSerializer::None,
+        loaded_addresses: OptionSerializer::Skip,
+        return_data: result.return_data.into(),
+        compute_units_consumed: result.units_consumed.into(),
+    });
+    data.slot = Some(simulation.context.slot);
+
+    let mut page = Page::new(output_format);
+    add_tx_data_to_page(&data, &mut page);
+    if let Some(return_data) = return_data {
+        page.add(ReturnData {
+            program: return_data
+                .program_id
+                .parse::<Pubkey>()
+                .unwrap_or_default()
+                .into(),
+            data: return_data.data.0,
+        });
+    }
+    page.add(SimulatedAccounts {
+        pre_state_slot,
+        accounts: writable_keys
+            .iter()
+            .map(|pubkey| {
+                let pre = account_keys
+                    .iter()
+                    .position(|key| key == pubkey)
+                    .and_then(|pos| pre_accounts[pos].as_ref());
+                let post = post_account(pubkey);
+                SimulatedAccount {
+                    pubkey: (*pubkey).into(),
+                    pre: pre.map(AccountState::from),
+                    post: post.as_ref().map(AccountState::from),
+                    data_changed: pre.map(|a| &a.data) != post.as_ref().map(|a| &a.data),
+                }
+            })
+            .collect(),
+    });
+    page.display();
+}
@@ -0,0 +1,403 @@
+use std::collections::HashMap;
+
+use borsh::BorshDeserialize;
+use mpl_core::instructions::{CreateV1InstructionArgs, CreateV2InstructionArgs};
+use mpl_token_metadata::{
+    instructions::{CreateInstructionArgs, CreateMetadataAccountV3InstructionArgs},
+    types::{CreateArgs, TokenStandard},
+};
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+use spl_token::instruction::TokenInstruction;
+
+use super::{
+    balance_change::{token_balances, TokenName},
+    fee::created_accounts,
+    instruction::TOKEN_2022_ID,
+    transfer::{decode_transfer, executed_instructions, token_accounts, RawTransfer},
+    TransactionData,
+};
+use crate::{
+    anchor::instruction_discriminator,
+    metaplex::bubblegum::BUBBLEGUM_ID,
+    output::Output,
+    pretty::{
+        amount::{PrettyAmount, SOL_DECIMALS},
+        public_key::PrettyPublicKey,
+    },
+};
+
+/// labels of `PrettyPublicKey` programs that swap tokens
+const DEX_LABELS: &[&str] = &[
+    "Swap Program",
+    "Serum Dex Program v3",
+    "Serum Dex Program v2",
+    "Serum Dex Program v1",
+    "Serum Swap Program",
+    "Raydium AMM Program",
+    "Raydium Liquidity Pool v1",
+    "Raydium Liquidity Pool v2",
+    "Orca Swap Program v1",
+    "Orca Swap Program v2",
+    "Saber Stable Swap Program",
+    "Saber Router Program",
+    "Step Finance Swap Program",
+    "Swim Swap Program",
+    "Mercurial Stable Swap Program",
+    "OpenBook Dex",
+    "OpenBook Dex V2",
+    "STEPN Dex",
+];
+
+/// One line description of what the transaction did, derived from the executed instructions
+/// and balance changes of the fee payer,
+/// e.g. "GfsJ..Wf9U swapped 1 SOL for 150 USDC via Raydium AMM"
+#[derive(Debug, Serialize)]
+pub struct TransactionSummary {
+    pub summary: String,
+}
+
+impl Output for TransactionSummary {
+    fn struct_name(&self) -> String {
+        String::from("TransactionSummary")
+    }
+
+    fn to_raw_struct(&self) -> String {
+        format!("{}: {}", self.struct_name(), self.summary)
+    }
+
+    fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+/// Instruction executed by the transaction as program id, accounts and data
+type ExecutedInstruction = (Pubkey, Vec<Pubkey>, Vec<u8>);
+
+impl TransactionSummary {
+    pub fn new(
+        data: &TransactionData,
+        account_keys: &[Pubkey],
+        token_names: &HashMap<Pubkey, TokenName>,
+    ) -> Self {
+        let actor = account_keys.first().copied().unwrap_or_default();
+        let instructions = executed_instructions(data, account_keys);
+        let narrative = describe_mint(&instructions)
+            .or_else(|| describe_swap(data, account_keys, &instructions, token_names))
+            .or_else(|| describe_transfers(data, account_keys, &instructions, token_names))
+            .or_else(|| describe_token_accounts(&instructions))
+            .unwrap_or_else(|| describe_programs(data, account_keys));
+        let failed = data.meta.as_ref().is_some_and(|meta| meta.err.is_some());
+        TransactionSummary {
+            summary: format!(
+                "{} {}{}",
+                short_address(&actor),
+                narrative,
+                if failed { " (failed)" } else { "" }
+            ),
+        }
+    }
+}
+
+/// first and last 4 characters of the address
+fn short_address(pubkey: &Pubkey) -> String {
+    let address = pubkey.to_string();
+    format!("{}..{}", &address[..4], &address[address.len() - 4..])
+}
+
+/// program label without the "Program" suffix or the short address of unknown programs
+fn program_name(program_id: &Pubkey) -> String {
+    match PrettyPublicKey::from(*program_id).label() {
+        Some(label) => label.trim_end_matches(" Program").to_string(),
+        None => short_address(program_id),
+    }
+}
+
+fn token_symbol(mint: &Pubkey, token_names: &HashMap<Pubkey, TokenName>) -> String {
+    if *mint == spl_token::native_mint::ID {
+        return String::from("SOL");
+    }
+    match token_names.get(mint) {
+        Some(token) if !token.symbol.is_empty() => token.symbol.clone(),
+        _ => short_address(mint),
+    }
+}
+
+/// NFT minted or token created with Metaplex programs,
+/// optional accounts of Core instructions are set to the program id when they are omitted
+fn describe_mint(instructions: &[ExecutedInstruction]) -> Option<String> {
+    let is_master_edition_created = instructions.iter().any(|(program_id, _, data)| {
+        *program_id == mpl_token_metadata::ID && data.first() == Some(&17)
+    });
+    let mint_v1 = instruction_discriminator("mint_v1");
+    let mint_to_collection_v1 = instruction_discriminator("mint_to_collection_v1");
+    let is_bubblegum_mint = |data: &[u8]| {
+        data.get(..8).is_some_and(|discriminator| {
+            discriminator == mint_v1 || discriminator == mint_to_collection_v1
+        })
+    };
+    instructions
+        .iter()
+        .find_map(|(program_id, accounts, data)| {
+            let (name, standard, collection) = match (*program_id, data.first()?) {
+                (mpl_core::ID, 0) => {
+                    let args = CreateV1InstructionArgs::deserialize(&mut data.get(1..)?).ok()?;
+                    (args.name, "Core", accounts.get(1).copied())
+                }
+                (mpl_core::ID, 20) => {
+                    let args = CreateV2InstructionArgs::deserialize(&mut data.get(1..)?).ok()?;
+                    (args.name, "Core", accounts.get(1).copied())
+                }
+                (mpl_token_metadata::ID, 42) => {
+                    let args = CreateInstructionArgs::deserialize(&mut data.get(1..)?).ok()?;
+                    let CreateArgs::V1 {
+                        name,
+                        token_standard,
+                        collection,
+                        ..
+                    } = args.create_args;
+                    let standard = match token_standard {
+                        TokenStandard::NonFungible | TokenStandard::NonFungibleEdition => {
+                            "Token Metadata"
+                        }
+                        TokenStandard::ProgrammableNonFungible
+                        | TokenStandard::ProgrammableNonFungibleEdition => "pNFT",
+                        _ => return Some(format!("created token '{}'", name)),
+                    };
+                    (name, standard, collection.map(|collection| collection.key))
+                }
+                (mpl_token_metadata::ID, 33) => {
+                    let args =
+                        CreateMetadataAccountV3InstructionArgs::deserialize(&mut data.get(1..)?)
+                            .ok()?;
+                    if !is_master_edition_created {
+                        return Some(format!("created token '{}'", args.data.name));
+                    }
+                    let collection = args.data.collection.map(|collection| collection.key);
+                    (args.data.name, "Token Metadata", collection)
+                }
+                (BUBBLEGUM_ID, _) if is_bubblegum_mint(data) => {
+                    // metadata args start with the name
+                    let name = String::deserialize(&mut data.get(8..)?).ok()?;
+                    let collection = match data[..8] == mint_to_collection_v1 {
+                        true => accounts.get(8).copied(),
+                        false => None,
+                    };
+                    (name, "cNFT", collection)
+                }
+                _ => return None,
+            };
+            let name = name.trim_end_matches('\0');
+            let collection = collection
+                .filter(|collection| collection != program_id)
+                .map(|collection| format!(" into collection {}", short_address(&collection)))
+                .unwrap_or_default();
+            Some(format!(
+                "minted NFT '{}' ({}){}",
+                name, standard, collection
+            ))
+        })
+}
+
+/// SOL and token balance changes of the fee payer, wrapped SOL is counted as SOL,
+/// the fee and rent of accounts created by the transaction are excluded,
+/// amounts are in base units with decimals
+fn fee_payer_changes(
+    data: &TransactionData,
+    account_keys: &[Pubkey],
+) -> Vec<(Option<Pubkey>, i128, u8)> {
+    let Some(meta) = &data.meta else {
+        return Vec::new();
+    };
+    let Some(fee_payer) = account_keys.first() else {
+        return Vec::new();
+    };
+    let rent: u64 = created_accounts(data, account_keys)
+        .iter()
+        .map(|account| account.rent)
+        .sum();
+    let mut sol = match (meta.pre_balances.first(), meta.post_balances.first()) {
+        (Some(pre), Some(post)) => *post as i128 - *pre as i128 + meta.fee as i128 + rent as i128,
+        _ => 0,
+    };
+    let mut tokens: Vec<(Option<Pubkey>, i128, u8)> = Vec::new();
+    let fee_payer = fee_payer.to_string();
+    let mut add = |mint: &str, amount: i128, decimals: u8| {
+        let Ok(mint) = mint.parse::<Pubkey>() else {
+            return;
+        };
+        if mint == spl_token::native_mint::ID {
+            sol += amount;
+            return;
+        }
+        match tokens.iter_mut().find(|(token, _, _)| *token == Some(mint)) {
+            Some((_, change, _)) => *change += amount,
+            None => tokens.push((Some(mint), amount, decimals)),
+        }
+    };
+    for (balances, sign) in [
+        (&meta.pre_token_balances, -1),
+        (&meta.post_token_balances, 1),
+    ] {
+        for balance in token_balances(balances) {
+            if Option::<String>::from(balance.owner.clone()).as_ref() != Some(&fee_payer) {
+                continue;
+            }
+            let amount = balance
+                .ui_token_amount
+                .amount
+                .parse::<i128>()
+                .unwrap_or_default();
+            add(
+                &balance.mint,
+                sign * amount,
+                balance.ui_token_amount.decimals,
+            );
+        }
+    }
+    let mut changes = vec![(None, sol, SOL_DECIMALS)];
+    changes.extend(tokens);
+    changes.retain(|(_, change, _)| *change != 0);
+    changes
+}
+
+fn format_amount(
+    mint: &Option<Pubkey>,
+    amount: i128,
+    decimals: u8,
+    token_names: &HashMap<Pubkey, TokenName>,
+) -> String {
+    let symbol = match mint {
+        Some(mint) => token_symbol(mint, token_names),
+        None => String::from("SOL"),
+    };
+    format!("{} {}", PrettyAmount::new(amount.abs(), decimals), symbol)
+}
+
+/// fee payer gave one asset and got another while a DEX program was invoked,
+/// token legs are preferred over SOL that also pays for tips and fees of the DEX
+fn describe_swap(
+    data: &TransactionData,
+    account_keys: &[Pubkey],
+    instructions: &[ExecutedInstruction],
+    token_names: &HashMap<Pubkey, TokenName>,
+) -> Option<String> {
+    let dex = instructions.iter().find_map(|(program_id, _, _)| {
+        let label = PrettyPublicKey::from(*program_id).label()?;
+        DEX_LABELS.contains(&label).then_some(program_id)
+    })?;
+    let changes = fee_payer_changes(data, account_keys);
+    // amounts of different mints are not comparable, the first token leg is taken
+    let leg = |is_sent: bool| {
+        let legs: Vec<&(Option<Pubkey>, i128, u8)> = changes
+            .iter()
+            .filter(|(_, change, _)| (*change < 0) == is_sent)
+            .collect();
+        legs.iter()
+            .find(|(mint, _, _)| mint.is_some())
+            .or(legs.first())
+            .copied()
+    };
+    let sent = leg(true)?;
+    let received = leg(false)?;
+    Some(format!(
+        "swapped {} for {} via {}",
+        format_amount(&sent.0, sent.1, sent.2, token_names),
+        format_amount(&received.0, received.1, received.2, token_names),
+        program_name(dex)
+    ))
+}
+
+/// SOL and token transfers from the fee payer,
+/// wrapping SOL into own token accounts is not a transfer
+fn describe_transfers(
+    data: &TransactionData,
+    account_keys: &[Pubkey],
+    instructions: &[ExecutedInstruction],
+    token_names: &HashMap<Pubkey, TokenName>,
+) -> Option<String> {
+    let fee_payer = account_keys.first()?;
+    let token_accounts = token_accounts(data, account_keys);
+    let owner_of = |pubkey: &Pubkey| {
+        token_accounts
+            .get(pubkey)
+            .and_then(|info| info.owner)
+            .unwrap_or(*pubkey)
+    };
+    let transfers: Vec<RawTransfer> = instructions
+        .iter()
+        .filter_map(|(program_id, accounts, data)| decode_transfer(program_id, accounts, data))
+        .filter(|transfer| owner_of(&transfer.from) == *fee_payer)
+        .filter(|transfer| owner_of(&transfer.to) != *fee_payer)
+        .collect();
+    let transfer = transfers.first()?;
+    let (mint, decimals) = match &transfer.token {
+        None => (None, SOL_DECIMALS),
+        Some(token) => {
+            let info = token_accounts
+                .get(&transfer.from)
+                .or_else(|| token_accounts.get(&transfer.to));
+            (
+                token.mint.or(info.map(|info| info.mint)),
+                token
+                    .decimals
+                    .or(info.map(|info| info.decimals))
+                    .unwrap_or_default(),
+            )
+        }
+    };
+    let others = match transfers.len() - 1 {
+        0 => String::new(),
+        1 => String::from(" and made 1 other transfer"),
+        count => format!(" and made {} other transfers", count),
+    };
+    Some(format!(
+        "sent {} to {}{}",
+        format_amount(&mint, transfer.amount as i128, decimals, token_names),
+        short_address(&owner_of(&transfer.to)),
+        others
+    ))
+}
+
+/// token accounts initialized directly or by Associated Token program
+fn describe_token_accounts(instructions: &[ExecutedInstruction]) -> Option<String> {
+    let count = instructions
+        .iter()
+        .filter(|(program_id, _, _)| *program_id == spl_token::ID || *program_id == TOKEN_2022_ID)
+        .filter(|(_, _, data)| {
+            matches!(
+                TokenInstruction::unpack(data),
+                Ok(TokenInstruction::InitializeAccount
+                    | TokenInstruction::InitializeAccount2 { .. }
+                    | TokenInstruction::InitializeAccount3 { .. })
+            )
+        })
+        .count();
+    match count {
+        0 => None,
+        1 => Some(String::from("created 1 token account")),
+        count => Some(format!("created {} token accounts", count)),
+    }
+}
+
+/// programs of the transaction instructions except Compute Budget
+fn describe_programs(data: &TransactionData, account_keys: &[Pubkey]) -> String {
+    let mut programs: Vec<Pubkey> = Vec::new();
+    for ix in data.transaction.message.instructions() {
+        let program_id = *ix.program_id(account_keys);
+        if program_id != solana_sdk::compute_budget::ID && !programs.contains(&program_id) {
+            programs.push(program_id);
+        }
+    }
+    match programs.is_empty() {
+        true => String::from("invoked no programs"),
+        false => format!(
+            "invoked {}",
+            programs
+                .iter()
+                .map(program_name)
+                .collect::<Vec<String>>()
+                .join(", ")
+        ),
+    }
+}
@@ -0,0 +1,705 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    process::exit,
+    str::FromStr,
+};
+
+use serde::Serialize;
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind},
+    rpc_request::TokenAccountsFilter,
+    rpc_response::RpcConfirmedTransactionStatusWithSignature,
+};
+use solana_sdk::{
+    program_utils::limited_deserialize, pubkey::Pubkey, signature::Signature,
+    system_instruction::SystemInstruction,
+};
+use solana_transaction_status::{
+    option_serializer::OptionSerializer, UiInstruction, UiTransactionTokenBalance,
+};
+use spl_token::instruction::TokenInstruction;
+
+use super::{
+    balance_change::{token_balances, TokenName},
+    instruction::TOKEN_2022_ID,
+    reader::{
+        get_token_names, get_tx_data, MergedSignaturePages, TransactionFetchError,
+        TransactionsPagination,
+    },
+    TransactionData,
+};
+use crate::{
+    output::{print_error, print_warning, Output, OutputFormat},
+    page::PageStream,
+    pretty::{
+        amount::{PrettyAmount, SOL_DECIMALS},
+        public_key::PrettyPublicKey,
+        table::{csv_line, format_table},
+        time::PrettyTimestamp,
+    },
+    rpc,
+};
+
+#[derive(Clone, Copy, Debug, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransferDirection {
+    In,
+    Out,
+    /// between the wallet and its own token accounts
+    Internal,
+}
+
+impl fmt::Display for TransferDirection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TransferDirection::In => write!(f, "in"),
+            TransferDirection::Out => write!(f, "out"),
+            TransferDirection::Internal => write!(f, "internal"),
+        }
+    }
+}
+
+/// SOL or token transfer of a wallet, `mint` is `None` for SOL,
+/// `fee` paid by the wallet is set on the first transfer of a transaction only
+#[derive(Debug, Serialize)]
+pub struct Transfer {
+    pub block_time: Option<PrettyTimestamp>,
+    pub signature: String,
+    pub direction: TransferDirection,
+    /// owner of the other token account if it is known, otherwise the account itself,
+    /// `None` when the balance changed without a transfer from or to a known account
+    pub counterparty: Option<PrettyPublicKey>,
+    pub mint: Option<PrettyPublicKey>,
+    pub symbol: Option<String>,
+    pub amount: PrettyAmount,
+    pub fee: Option<PrettyAmount>,
+}
+
+impl Transfer {
+    fn to_row(&self, time: impl Fn(PrettyTimestamp) -> String) -> Vec<String> {
+        vec![
+            self.block_time.map(time).unwrap_or_default(),
+            self.signature.clone(),
+            self.direction.to_string(),
+            self.counterparty
+                .as_ref()
+                .map(|counterparty| counterparty.pubkey().to_string())
+                .unwrap_or_default(),
+            self.mint
+                .as_ref()
+                .map(|mint| mint.pubkey().to_string())
+                .unwrap_or_default(),
+            self.symbol.clone().unwrap_or_default(),
+            self.amount.to_string(),
+            self.fee.map(|fee| fee.to_string()).unwrap_or_default(),
+        ]
+    }
+}
+
+impl Output for Transfer {
+    fn struct_name(&self) -> String {
+        String::from("Transfer")
+    }
+
+    fn to_raw_struct(&self) -> String {
+        format!("{:#?}", self)
+    }
+
+    fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+/// Transfers of a wallet from the newest, one table row per transfer in raw output
+#[derive(Debug, Serialize)]
+pub struct WalletTransfers {
+    pub wallet: PrettyPublicKey,
+    pub transfers: Vec<Transfer>,
+}
+
+impl Output for WalletTransfers {
+    fn struct_name(&self) -> String {
+        String::from("WalletTransfers")
+    }
+
+    fn to_raw_struct(&self) -> String {
+        let rows: Vec<Vec<String>> = std::iter::once(
+            [
+                "DATE",
+                "SIGNATURE",
+                "DIRECTION",
+                "COUNTERPARTY",
+                "MINT",
+                "SYMBOL",
+                "AMOUNT",
+                "FEE",
+            ]
+            .map(String::from)
+            .to_vec(),
+        )
+        .chain(
+            self.transfers
+                .iter()
+                .map(|transfer| transfer.to_row(|time| time.to_string())),
+        )
+        .collect();
+        format!(
+            "{} of {:?}\n{}",
+            self.struct_name(),
+            self.wallet,
+            format_table(&rows)
+        )
+    }
+
+    fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+/// Token account state known from token balances of the transaction meta
+pub struct TokenAccountInfo {
+    pub mint: Pubkey,
+    pub owner: Option<Pubkey>,
+    pub decimals: u8,
+}
+
+/// Token accounts of the transaction by their addresses, accounts closed by the transaction
+/// are known from pre balances and created ones from post balances
+pub fn token_accounts(
+    data: &TransactionData,
+    account_keys: &[Pubkey],
+) -> HashMap<Pubkey, TokenAccountInfo> {
+    let Some(meta) = &data.meta else {
+        return HashMap::new();
+    };
+    token_balances(&meta.pre_token_balances)
+        .iter()
+        .chain(token_balances(&meta.post_token_balances))
+        .filter_map(|balance| {
+            let pubkey = *account_keys.get(balance.account_index as usize)?;
+            let owner = Option::<String>::from(balance.owner.clone())
+                .and_then(|owner| owner.parse::<Pubkey>().ok());
+            let info = TokenAccountInfo {
+                mint: balance.mint.parse().ok()?,
+                owner,
+                decimals: balance.ui_token_amount.decimals,
+            };
+            Some((pubkey, info))
+        })
+        .collect()
+}
+
+/// Outer instructions each followed by instructions it invoked at any depth,
+/// as program id, accounts and data
+pub fn executed_instructions(
+    data: &TransactionData,
+    account_keys: &[Pubkey],
+) -> Vec<(Pubkey, Vec<Pubkey>, Vec<u8>)> {
+    let key = |idx: u8| account_keys.get(idx as usize).copied().unwrap_or_default();
+    let inner_instructions = match data.meta.as_ref().map(|meta| &meta.inner_instructions) {
+        Some(OptionSerializer::Some(inner_instructions)) => inner_instructions.as_slice(),
+        _ => &[],
+    };
+    let mut instructions = Vec::new();
+    for (outer_idx, ix) in data.transaction.message.instructions().iter().enumerate() {
+        instructions.push((
+            key(ix.program_id_index),
+            ix.accounts.iter().map(|idx| key(*idx)).collect(),
+            ix.data.clone(),
+        ));
+        let invoked = inner_instructions
+            .iter()
+            .filter(|inner| inner.index as usize == outer_idx)
+            .flat_map(|inner| inner.instructions.iter())
+            .filter_map(|ix| match ix {
+                UiInstruction::Compiled(ix) => Some(ix),
+                UiInstruction::Parsed(_) => None,
+            });
+        for ix in invoked {
+            instructions.push((
+                key(ix.program_id_index),
+                ix.accounts.iter().map(|idx| key(*idx)).collect(),
+                bs58::decode(&ix.data).into_vec().unwrap_or_default(),
+            ));
+        }
+    }
+    instructions
+}
+
+/// Transfer decoded from an instruction, token amounts are in base units
+pub struct RawTransfer {
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub amount: u64,
+    /// `None` for SOL transfers
+    pub token: Option<RawTokenTransfer>,
+}
+
+pub struct RawTokenTransfer {
+    /// the mint is not an account of `Transfer` instruction, it comes from token balances then
+    pub mint: Option<Pubkey>,
+    pub decimals: Option<u8>,
+}
+
+pub fn decode_transfer(
+    program_id: &Pubkey,
+    accounts: &[Pubkey],
+    data: &[u8],
+) -> Option<RawTransfer> {
+    let account = |idx: usize| accounts.get(idx).copied();
+    match *program_id {
+        solana_sdk::system_program::ID => {
+            let (from, to, lamports) = match limited_deserialize::<SystemInstruction>(data).ok()? {
+                SystemInstruction::Transfer { lamports } => (account(0)?, account(1)?, lamports),
+                SystemInstruction::TransferWithSeed { lamports, .. } => {
+                    (account(0)?, account(2)?, lamports)
+                }
+                SystemInstruction::WithdrawNonceAccount(lamports) => {
+                    (account(0)?, account(1)?, lamports)
+                }
+                _ => return None,
+            };
+            Some(RawTransfer {
+                from,
+                to,
+                amount: lamports,
+                token: None,
+            })
+        }
+        // Token-2022 shares instruction layout with Token program
+        spl_token::ID | TOKEN_2022_ID => match TokenInstruction::unpack(data).ok()? {
+            TokenInstruction::Transfer { amount } => Some(RawTransfer {
+                from: account(0)?,
+                to: account(1)?,
+                amount,
+                token: Some(RawTokenTransfer {
+                    mint: None,
+                    decimals: None,
+                }),
+            }),
+            TokenInstruction::TransferChecked { amount, decimals } => Some(RawTransfer {
+                from: account(0)?,
+                to: account(2)?,
+                amount,
+                token: Some(RawTokenTransfer {
+                    mint: account(1),
+                    decimals: Some(decimals),
+                }),
+            }),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Transfer of the wallet before it is named, `counterparty` is `None` when
+/// the balance changed without a decoded transfer and no account matches the change
+struct WalletTransfer {
+    direction: TransferDirection,
+    counterparty: Option<Pubkey>,
+    mint: Option<Pubkey>,
+    decimals: u8,
+    amount: i128,
+}
+
+/// Token amount of each token account in token balances, in base units
+fn token_amounts(
+    balances: &OptionSerializer<Vec<UiTransactionTokenBalance>>,
+    account_keys: &[Pubkey],
+) -> HashMap<Pubkey, i128> {
+    token_balances(balances)
+        .iter()
+        .filter_map(|balance| {
+            let pubkey = *account_keys.get(balance.account_index as usize)?;
+            Some((pubkey, balance.ui_token_amount.amount.parse().ok()?))
+        })
+        .collect()
+}
+
+/// Wallet transfers of a transaction, token accounts are attributed to their owners
+/// by token balances, transfers of failed transactions are reverted and not listed.
+/// Balance changes of the wallet and its token accounts not explained by decoded transfers
+/// (e.g. lamports debited by a program, refunds of closed accounts, mints, burns
+/// or transfer fees of Token-2022) are listed as transfers of the remaining amount
+fn wallet_transfers(
+    data: &TransactionData,
+    status: &RpcConfirmedTransactionStatusWithSignature,
+    wallet: &Pubkey,
+    wallet_token_accounts: &HashSet<Pubkey>,
+    token_names: &mut HashMap<Pubkey, Option<TokenName>>,
+) -> Vec<Transfer> {
+    let Some(meta) = &data.meta else {
+        return Vec::new();
+    };
+    if meta.err.is_some() {
+        return Vec::new();
+    }
+    let account_keys = data.account_keys();
+    let token_accounts = token_accounts(data, &account_keys);
+    let is_own = |pubkey: &Pubkey| {
+        pubkey == wallet
+            || wallet_token_accounts.contains(pubkey)
+            || token_accounts
+                .get(pubkey)
+                .is_some_and(|info| info.owner.as_ref() == Some(wallet))
+    };
+    let owner_of = |pubkey: &Pubkey| {
+        token_accounts
+            .get(pubkey)
+            .and_then(|info| info.owner)
+            .unwrap_or(*pubkey)
+    };
+    let direction = |from_own: bool, to_own: bool| match (from_own, to_own) {
+        (true, true) => TransferDirection::Internal,
+        (true, false) => TransferDirection::Out,
+        _ => TransferDirection::In,
+    };
+
+    let raw_transfers: Vec<RawTransfer> = executed_instructions(data, &account_keys)
+        .iter()
+        .filter_map(|(program_id, accounts, data)| decode_transfer(program_id, accounts, data))
+        .filter(|transfer| is_own(&transfer.from) || is_own(&transfer.to))
+        .collect();
+    let mut transfers: Vec<WalletTransfer> = raw_transfers
+        .iter()
+        .map(|transfer| {
+            let direction = direction(is_own(&transfer.from), is_own(&transfer.to));
+            let counterparty = match direction {
+                TransferDirection::In => owner_of(&transfer.from),
+                _ => owner_of(&transfer.to),
+            };
+            let (mint, decimals) = match &transfer.token {
+                None => (None, SOL_DECIMALS),
+                Some(token) => {
+                    let info = token_accounts
+                        .get(&transfer.from)
+                        .or_else(|| token_accounts.get(&transfer.to));
+                    let mint = token.mint.or(info.map(|info| info.mint));
+                    let decimals = token
+                        .decimals
+                        .or(info.map(|info| info.decimals))
+                        .unwrap_or_default();
+                    (mint, decimals)
+                }
+            };
+            WalletTransfer {
+                direction,
+                counterparty: Some(counterparty),
+                mint,
+                decimals,
+                amount: transfer.amount as i128,
+            }
+        })
+        .collect();
+
+    // change of an account not explained by decoded transfers to and from it
+    let remainder = |account: &Pubkey, change: i128, is_token: bool| {
+        let decoded: i128 = raw_transfers
+            .iter()
+            .filter(|transfer| transfer.token.is_some() == is_token)
+            .map(|transfer| {
+                let amount = transfer.amount as i128;
+                match (transfer.from == *account, transfer.to == *account) {
+                    (false, true) => amount,
+                    (true, false) => -amount,
+                    _ => 0,
+                }
+            })
+            .sum();
+        change - decoded
+    };
+    let sol_changes: Vec<(Pubkey, i128)> = account_keys
+        .iter()
+        .zip(meta.pre_balances.iter().zip(meta.post_balances.iter()))
+        .enumerate()
+        .map(|(idx, (pubkey, (pre, post)))| {
+            let mut change = *post as i128 - *pre as i128;
+            if idx == 0 {
+                change += meta.fee as i128;
+            }
+            (*pubkey, change)
+        })
+        .collect();
+    if let Some((_, change)) = sol_changes.iter().find(|(pubkey, _)| pubkey == wallet) {
+        let remainder = remainder(wallet, *change, false);
+        if remainder != 0 {
+            let counterparty = sol_changes
+                .iter()
+                .find(|(pubkey, change)| pubkey != wallet && *change == -remainder)
+                .map(|(pubkey, _)| *pubkey);
+            let counterparty_is_own = counterparty.is_some_and(|pubkey| is_own(&pubkey));
+            transfers.push(WalletTransfer {
+                direction: match remainder > 0 {
+                    true => direction(counterparty_is_own, true),
+                    false => direction(true, counterparty_is_own),
+                },
+                counterparty,
+                mint: None,
+                decimals: SOL_DECIMALS,
+                amount: remainder.abs(),
+            });
+        }
+    }
+    let pre_amounts = token_amounts(&meta.pre_token_balances, &account_keys);
+    let post_amounts = token_amounts(&meta.post_token_balances, &account_keys);
+    let token_changes: Vec<(Pubkey, i128)> = account_keys
+        .iter()
+        .filter(|pubkey| token_accounts.contains_key(pubkey))
+        .map(|pubkey| {
+            let amount = |amounts: &HashMap<Pubkey, i128>| amounts.get(pubkey).copied();
+            let change = amount(&post_amounts).unwrap_or_default()
+                - amount(&pre_amounts).unwrap_or_default();
+            (*pubkey, change)
+        })
+        .collect();
+    for (account, change) in token_changes.iter().filter(|(pubkey, _)| is_own(pubkey)) {
+        let remainder = remainder(account, *change, true);
+        if remainder == 0 {
+            continue;
+        }
+        let info = &token_accounts[account];
+        let counterparty = token_changes
+            .iter()
+            .filter(|(pubkey, _)| !is_own(pubkey))
+            .filter(|(pubkey, _)| token_accounts[pubkey].mint == info.mint)
+            .find(|(_, change)| *change == -remainder)
+            .map(|(pubkey, _)| owner_of(pubkey));
+        transfers.push(WalletTransfer {
+            direction: match remainder > 0 {
+                true => TransferDirection::In,
+                false => TransferDirection::Out,
+            },
+            counterparty,
+            mint: Some(info.mint),
+            decimals: info.decimals,
+            amount: remainder.abs(),
+        });
+    }
+
+    let unnamed_mints: Vec<Pubkey> = transfers
+        .iter()
+        .filter_map(|transfer| transfer.mint)
+        .filter(|mint| !token_names.contains_key(mint))
+        .collect::<HashSet<Pubkey>>()
+        .into_iter()
+        .collect();
+    let mut fetched_names = get_token_names(&unnamed_mints);
+    for mint in unnamed_mints {
+        token_names.insert(mint, fetched_names.remove(&mint));
+    }
+
+    let mut fee = (account_keys.first() == Some(wallet))
+        .then(|| PrettyAmount::new(meta.fee as i128, SOL_DECIMALS));
+    transfers
+        .into_iter()
+        .map(|transfer| {
+            let symbol = match transfer.mint {
+                None => Some(String::from("SOL")),
+                Some(mint) => token_names
+                    .get(&mint)
+                    .cloned()
+                    .flatten()
+                    .map(|name| name.symbol),
+            };
+            Transfer {
+                block_time: status.block_time.map(PrettyTimestamp::from),
+                signature: status.signature.clone(),
+                direction: transfer.direction,
+                counterparty: transfer.counterparty.map(PrettyPublicKey::from),
+                mint: transfer.mint.map(PrettyPublicKey::from),
+                symbol,
+                amount: PrettyAmount::new(transfer.amount, transfer.decimals),
+                fee: fee.take(),
+            }
+        })
+        .collect()
+}
+
+/// Token accounts the wallet owns now, of Token and Token-2022 programs
+fn get_wallet_token_accounts(wallet: &Pubkey) -> Result<Vec<Pubkey>, ClientError> {
+    let mut token_accounts = Vec::new();
+    for program_id in [spl_token::ID, TOKEN_2022_ID] {
+        let accounts = rpc::send_rate_limited(|rpc_con| {
+            rpc_con.get_token_accounts_by_owner(wallet, TokenAccountsFilter::ProgramId(program_id))
+        })?;
+        token_accounts.extend(
+            accounts
+                .iter()
+                .filter_map(|account| account.pubkey.parse::<Pubkey>().ok()),
+        );
+    }
+    Ok(token_accounts)
+}
+
+/// Transfers output, the table and JSON are displayed when the history is read,
+/// NDJSON and CSV rows as soon as they are fetched
+struct TransferListing {
+    csv: bool,
+    stream: PageStream,
+    transfers: WalletTransfers,
+    failures: Vec<TransactionFetchError>,
+}
+
+impl TransferListing {
+    fn new(wallet: &Pubkey, csv: bool, output_format: OutputFormat) -> Self {
+        if csv {
+            println!("date,signature,direction,counterparty,mint,symbol,amount,fee");
+        }
+        TransferListing {
+            csv,
+            stream: PageStream::new(output_format),
+            transfers: WalletTransfers {
+                wallet: (*wallet).into(),
+                transfers: Vec::new(),
+            },
+            failures: Vec::new(),
+        }
+    }
+
+    fn is_row_streamed(&self) -> bool {
+        matches!(self.stream.format(), OutputFormat::AsNdJson)
+    }
+
+    fn add(&mut self, transfer: Transfer) {
+        if self.csv {
+            println!("{}", csv_line(&transfer.to_row(|time| time.to_rfc3339())));
+        } else if self.is_row_streamed() {
+            let mut page = self.stream.page();
+            page.add(transfer);
+            self.stream.push(page);
+        } else {
+            self.transfers.transfers.push(transfer);
+        }
+    }
+
+    fn add_failure(&mut self, failure: TransactionFetchError) {
+        if self.csv {
+            // keep stdout a valid CSV
+            eprintln!(
+                "can't fetch transaction {}: {}",
+                failure.signature, failure.error
+            );
+        } else if self.is_row_streamed() {
+            let mut page = self.stream.page();
+            page.add(failure);
+            self.stream.push(page);
+        } else {
+            self.failures.push(failure);
+        }
+    }
+
+    fn finish(self) {
+        if self.csv {
+            return;
+        }
+        if self.is_row_streamed() {
+            self.stream.finish();
+            return;
+        }
+        let mut page = self.stream.page();
+        page.add(self.transfers);
+        self.failures
+            .into_iter()
+            .for_each(|failure| page.add(failure));
+        page.display();
+    }
+}
+
+/// List SOL and token transfers of the wallet and its token accounts from the newest transaction,
+/// transfers are decoded from System and Token program instructions at any depth.
+/// Histories of the wallet and its current token accounts are merged,
+/// `limit` counts transactions with wallet transfers
+pub fn list_transfers(
+    address: &str,
+    pagination: &TransactionsPagination,
+    concurrency: usize,
+    csv: bool,
+    output_format: OutputFormat,
+) {
+    let wallet = match Pubkey::from_str(address) {
+        Ok(pubkey) => pubkey,
+        Err(_) => {
+            print_warning(
+                format!("address {:?} is not a valid solana public key", address).as_str(),
+            );
+            exit(1);
+        }
+    };
+    if let Some(cursor) = pagination.invalid_cursor() {
+        print_warning(format!("signature {:?} is not a valid", cursor).as_str());
+        exit(1);
+    }
+    let wallet_token_accounts = match get_wallet_token_accounts(&wallet) {
+        Ok(token_accounts) => token_accounts,
+        Err(err) => {
+            print_error(err);
+            exit(1);
+        }
+    };
+
+    let history_accounts: Vec<Pubkey> = std::iter::once(wallet)
+        .chain(wallet_token_accounts.iter().copied())
+        .collect();
+    let wallet_token_accounts: HashSet<Pubkey> = wallet_token_accounts.into_iter().collect();
+    let mut signature_pages = MergedSignaturePages::new(&history_accounts, pagination);
+    let mut listing = TransferListing::new(&wallet, csv, output_format);
+    let mut token_names = HashMap::new();
+    let limit = pagination.max_count();
+    let mut listed = 0;
+    while listed < limit {
+        let statuses = match signature_pages.next(limit - listed) {
+            Ok(statuses) => statuses,
+            Err(err) => {
+                print_error(err);
+                exit(1);
+            }
+        };
+        if statuses.is_empty() {
+            break;
+        }
+        // failed transactions don't transfer anything
+        let statuses: Vec<RpcConfirmedTransactionStatusWithSignature> = statuses
+            .into_iter()
+            .filter(|status| status.err.is_none())
+            .collect();
+        let signatures: Vec<&str> = statuses
+            .iter()
+            .map(|status| status.signature.as_str())
+            .collect();
+        rpc::fetch_concurrently(
+            &signatures,
+            concurrency,
+            |signature| {
+                let sig = Signature::from_str(signature)
+                    .map_err(|err| ClientError::from(ClientErrorKind::Custom(err.to_string())))?;
+                get_tx_data(&sig)
+            },
+            |idx, data| {
+                let status = &statuses[idx];
+                match data {
+                    Ok(data) => {
+                        let transfers = wallet_transfers(
+                            &data,
+                            status,
+                            &wallet,
+                            &wallet_token_accounts,
+                            &mut token_names,
+                        );
+                        if !transfers.is_empty() {
+                            transfers
+                                .into_iter()
+                                .for_each(|transfer| listing.add(transfer));
+                            listed += 1;
+                        }
+                    }
+                    Err(err) => {
+                        listing.add_failure(TransactionFetchError::new(&status.signature, err))
+                    }
+                }
+                listed < limit
+            },
+        );
+    }
+    listing.finish();
+}
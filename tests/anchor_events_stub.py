@@ -0,0 +1,199 @@
+# Local JSON RPC server for the Anchor events test of tests/transactions_test.sh,
+# every simulated transaction logs events of two Anchor programs, one with new (>= 0.30)
+# and one with legacy IDL format published on-chain, and data that is not an event
+import base64
+import hashlib
+import http.server
+import json
+import struct
+import sys
+import zlib
+
+PORT = int(sys.argv[1]) if len(sys.argv) > 1 else 8891
+ALPHABET = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz"
+
+WHIRLPOOL = "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc"
+JUPITER = "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4"
+USDC = "EPjFWdd5AvaAZ8FpLLNcp7D6WkVcJGQ2WWtLGrM5Kn8e"
+WALLET = "GfsJWjmGXMfct8JMR9Lm9ySUnniZbnGUTQDbT8ipWf9U"
+# IDL account addresses, derived from program ids with "anchor:idl" seed
+IDL_ADDRESSES = {
+    WHIRLPOOL: "2KFqE4RWoPVbvodo8vbggCFeHPS8TDvgpwp79ALMrcyn",
+    JUPITER: "C88XWfp26heEmDkmfSzeXP7Fd7GQJ2j9dDTUsyiZbUTa",
+}
+
+
+def pubkey(address):
+    value = 0
+    for char in address:
+        value = value * 58 + ALPHABET.index(char)
+    return value.to_bytes(32, "big")
+
+
+def borsh_string(value):
+    data = value.encode()
+    return struct.pack("<I", len(data)) + data
+
+
+def discriminator(name):
+    return hashlib.sha256(f"event:{name}".encode()).digest()[:8]
+
+
+TRADED = {
+    "name": "Traded",
+    "type": {
+        "kind": "struct",
+        "fields": [
+            {"name": "whirlpool", "type": "pubkey"},
+            {"name": "a_to_b", "type": "bool"},
+            {"name": "input_amount", "type": "u64"},
+            {"name": "fee", "type": {"option": "u16"}},
+            {"name": "side", "type": {"defined": {"name": "Side"}}},
+            {"name": "ticks", "type": {"vec": "i32"}},
+            {"name": "price", "type": {"defined": {"name": "Price"}}},
+            {"name": "memo", "type": "string"},
+        ],
+    },
+}
+IDLS = {
+    WHIRLPOOL: {
+        "address": WHIRLPOOL,
+        "metadata": {"name": "whirlpool", "version": "0.1.0", "spec": "0.1.0"},
+        "instructions": [],
+        "events": [{"name": "Traded", "discriminator": list(discriminator("Traded"))}],
+        "types": [
+            TRADED,
+            {
+                "name": "Side",
+                "type": {
+                    "kind": "enum",
+                    "variants": [
+                        {"name": "Buy"},
+                        {"name": "Sell", "fields": [{"name": "limit", "type": "u64"}]},
+                    ],
+                },
+            },
+            {"name": "Price", "type": {"kind": "struct", "fields": ["u64", "i8"]}},
+            # a type the explorer can't read doesn't break the rest of the IDL
+            {"name": "Unknown", "type": 42},
+        ],
+    },
+    JUPITER: {
+        "version": "0.1.0",
+        "name": "jupiter",
+        "instructions": [],
+        "events": [
+            {
+                "name": "SwapEvent",
+                "fields": [
+                    {"name": "amm", "type": "publicKey", "index": False},
+                    {"name": "inputMint", "type": "publicKey", "index": False},
+                    {"name": "inputAmount", "type": "u64", "index": False},
+                ],
+            }
+        ],
+    },
+}
+
+TRADED_DATA = (
+    discriminator("Traded")
+    + pubkey(WALLET)
+    + b"\x01"
+    + struct.pack("<Q", 1500000)
+    + b"\x01"
+    + struct.pack("<H", 30)
+    + b"\x01"
+    + struct.pack("<Q", 99)
+    + struct.pack("<Iii", 2, -5, 7)
+    + struct.pack("<Qb", 12345, -2)
+    + borsh_string("hi")
+)
+SWAP_DATA = discriminator("SwapEvent") + pubkey(WHIRLPOOL) + pubkey(USDC) + struct.pack("<Q", 42)
+
+
+def program_data(data):
+    return "Program data: " + base64.b64encode(data).decode()
+
+
+LOGS = [
+    f"Program {JUPITER} invoke [1]",
+    "Program log: Instruction: Route",
+    f"Program {WHIRLPOOL} invoke [2]",
+    "Program log: Instruction: Swap",
+    program_data(TRADED_DATA),
+    # the event with extra bytes after its fields is not decoded
+    program_data(TRADED_DATA + b"\x00"),
+    f"Program {WHIRLPOOL} consumed 20000 of 180000 compute units",
+    f"Program {WHIRLPOOL} success",
+    program_data(SWAP_DATA),
+    f"Program {JUPITER} consumed 40000 of 200000 compute units",
+    f"Program {JUPITER} success",
+]
+
+
+def idl_account(idl):
+    compressed = zlib.compress(json.dumps(idl).encode())
+    # discriminator, authority, length of compressed IDL JSON
+    data = bytes(40) + struct.pack("<I", len(compressed)) + compressed
+    return {
+        "data": [base64.b64encode(data).decode(), "base64"],
+        "executable": False,
+        "lamports": 1,
+        "owner": WHIRLPOOL,
+        "rentEpoch": 0,
+        "space": len(data),
+    }
+
+
+def system_account(lamports):
+    return {
+        "data": ["", "base64"],
+        "executable": False,
+        "lamports": lamports,
+        "owner": "11111111111111111111111111111111",
+        "rentEpoch": 0,
+        "space": 0,
+    }
+
+
+IDL_ACCOUNTS = {IDL_ADDRESSES[program]: idl_account(idl) for program, idl in IDLS.items()}
+
+
+class Handler(http.server.BaseHTTPRequestHandler):
+    def log_message(self, *args):
+        pass
+
+    def do_POST(self):
+        request = json.loads(self.rfile.read(int(self.headers["Content-Length"])))
+        method, params = request["method"], request["params"]
+        context = {"slot": 1}
+        response = {"jsonrpc": "2.0", "id": request["id"]}
+        if method == "getAccountInfo":
+            response["result"] = {"context": context, "value": IDL_ACCOUNTS.get(params[0])}
+        elif method == "getMultipleAccounts":
+            accounts = [system_account(1000000000) for _ in params[0]]
+            response["result"] = {"context": context, "value": accounts}
+        elif method == "simulateTransaction":
+            accounts = [system_account(1000000000) for _ in params[1]["accounts"]["addresses"]]
+            response["result"] = {
+                "context": context,
+                "value": {
+                    "err": None,
+                    "logs": LOGS,
+                    "accounts": accounts,
+                    "unitsConsumed": 60000,
+                    "returnData": None,
+                    "innerInstructions": [],
+                },
+            }
+        else:
+            response["error"] = {"code": -32601, "message": "Method not found"}
+        data = json.dumps(response).encode()
+        self.send_response(200)
+        self.send_header("Content-Type", "application/json")
+        self.send_header("Content-Length", str(len(data)))
+        self.end_headers()
+        self.wfile.write(data)
+
+
+http.server.ThreadingHTTPServer(("127.0.0.1", PORT), Handler).serve_forever()
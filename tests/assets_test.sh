@@ -0,0 +1,23 @@
+set -e
+
+SE=./target/debug/se
+
+echo "\n\nassets by owner"
+$SE assets --owner 4DRqQb3ihANJRqHNoenq9gTwp58rVn6jHMg5wiJYsQzf --limit 10
+sleep 0.5
+
+echo "\n\nassets by collection with json format"
+$SE assets --collection C3JoGupodf1s7JhCKiLNv2hbPRqYkDjxebVmsJh2EtZC --limit 5 -f json
+sleep 0.5
+
+echo "\n\nsearch compressed assets by owner"
+$SE assets --owner 4DRqQb3ihANJRqHNoenq9gTwp58rVn6jHMg5wiJYsQzf --compressed true --limit 5
+sleep 0.5
+
+echo "\n\ncNFT merkle tree and leaf index"
+$SE cnft-id 9HNN54hfD3GVy4WkUtXjJdxaTo9tjFzYmEXYN9eHnLZp
+sleep 0.5
+
+echo "\n\nassets by owner as NDJSON"
+$SE assets --owner 4DRqQb3ihANJRqHNoenq9gTwp58rVn6jHMg5wiJYsQzf --limit 10 -f ndjson
+sleep 0.5
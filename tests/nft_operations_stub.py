@@ -0,0 +1,109 @@
+# Local JSON RPC server for the NFT operations test of tests/transactions_test.sh,
+# every simulated transaction creates a Core asset and mints a cNFT, Bubblegum logs
+# the leaf schema of the minted cNFT with Noop program in the inner instructions
+import http.server
+import json
+import struct
+import sys
+
+PORT = int(sys.argv[1]) if len(sys.argv) > 1 else 8892
+ALPHABET = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz"
+
+WALLET = "GfsJWjmGXMfct8JMR9Lm9ySUnniZbnGUTQDbT8ipWf9U"
+CNFT_ASSET_ID = "8ZwiPzkWAuWbtgMdMdnPH7ziMtWjLBB2N1YXqdqHeXCx"
+# index of Bubblegum mint among the transaction instructions and of Noop program
+# among the transaction accounts
+MINT_INSTRUCTION = 1
+NOOP_ACCOUNT = 8
+
+
+def pubkey(address):
+    value = 0
+    for char in address:
+        value = value * 58 + ALPHABET.index(char)
+    return value.to_bytes(32, "big")
+
+
+def base58(data):
+    value = int.from_bytes(data, "big")
+    encoded = ""
+    while value:
+        value, rem = divmod(value, 58)
+        encoded = ALPHABET[rem] + encoded
+    return "1" * (len(data) - len(data.lstrip(b"\0"))) + encoded
+
+
+# LeafSchemaEvent: event type, version, LeafSchema::V1 with id, owner, delegate, nonce,
+# data hash and creator hash, followed by the leaf hash
+LEAF_SCHEMA = (
+    bytes([1, 0, 0])
+    + pubkey(CNFT_ASSET_ID)
+    + pubkey(WALLET)
+    + pubkey(WALLET)
+    + struct.pack("<Q", 7)
+    + bytes(96)
+)
+# AccountCompressionEvent::ApplicationData(ApplicationDataEvent::V1)
+APPLICATION_DATA = bytes([1, 0]) + struct.pack("<I", len(LEAF_SCHEMA)) + LEAF_SCHEMA
+# AccountCompressionEvent::ChangeLog logged by the tree append, it is not a leaf schema
+CHANGE_LOG = bytes([0, 1]) + bytes(32)
+
+
+def noop(data):
+    return {"programIdIndex": NOOP_ACCOUNT, "accounts": [], "data": base58(data), "stackHeight": 2}
+
+
+def system_account(lamports):
+    return {
+        "data": ["", "base64"],
+        "executable": False,
+        "lamports": lamports,
+        "owner": "11111111111111111111111111111111",
+        "rentEpoch": 0,
+        "space": 0,
+    }
+
+
+class Handler(http.server.BaseHTTPRequestHandler):
+    def log_message(self, *args):
+        pass
+
+    def do_POST(self):
+        request = json.loads(self.rfile.read(int(self.headers["Content-Length"])))
+        method, params = request["method"], request["params"]
+        context = {"slot": 1}
+        response = {"jsonrpc": "2.0", "id": request["id"]}
+        if method == "getAccountInfo":
+            # no IDLs are published
+            response["result"] = {"context": context, "value": None}
+        elif method == "getMultipleAccounts":
+            accounts = [system_account(1000000000) for _ in params[0]]
+            response["result"] = {"context": context, "value": accounts}
+        elif method == "getAsset":
+            # DAS API is supported but knows no assets
+            response["error"] = {"code": -32000, "message": "Asset not found"}
+        elif method == "simulateTransaction":
+            accounts = [system_account(1000000000) for _ in params[1]["accounts"]["addresses"]]
+            inner = [noop(APPLICATION_DATA), noop(CHANGE_LOG)]
+            response["result"] = {
+                "context": context,
+                "value": {
+                    "err": None,
+                    "logs": [],
+                    "accounts": accounts,
+                    "unitsConsumed": 80000,
+                    "returnData": None,
+                    "innerInstructions": [{"index": MINT_INSTRUCTION, "instructions": inner}],
+                },
+            }
+        else:
+            response["error"] = {"code": -32601, "message": "Method not found"}
+        data = json.dumps(response).encode()
+        self.send_response(200)
+        self.send_header("Content-Type", "application/json")
+        self.send_header("Content-Length", str(len(data)))
+        self.end_headers()
+        self.wfile.write(data)
+
+
+http.server.ThreadingHTTPServer(("127.0.0.1", PORT), Handler).serve_forever()
@@ -0,0 +1,111 @@
+# Local JSON RPC and off-chain metadata server for tests/offchain_test.sh,
+# every account is an MPL Core asset with the name and URI from ASSETS,
+# off-chain JSON files are served from OFFCHAIN by request path
+import base64
+import http.server
+import json
+import sys
+
+PORT = int(sys.argv[1]) if len(sys.argv) > 1 else 8890
+HOST = f"http://127.0.0.1:{PORT}"
+MPL_CORE_ID = "CoREENxT6tW1HoK8ypY1SxRMZTcVPm7R94rH4PZNhX7d"
+CID = "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"
+ARWEAVE_ID = "Sd3jkE3eKMpM-h2Dx1C4qQkNuyBb35ybBDxMSnFvCEs"
+
+ASSETS = {
+    "4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi": ("Stub #1", f"{HOST}/valid.json"),
+    "8qbHbw2BbbTHBW1sbeqakYXVKRQM8Ne7pLK7m6CVfeR": ("Stub #2", f"{HOST}/invalid.json"),
+    "CktRuQ2mttgRGkXJtyksdKHjUdc2C4TgDzyB98oEzy8": ("Stub #3", f"ipfs://{CID}/3.json"),
+    "GgBaCs3NCBuZN12kCJgAW63ydqohFkHEdfdEXBPzLHq": ("Stub #4", f"{HOST}/missing.json"),
+    "3kC1bzSUJbwhJFcxHBMpyJGr3UcRN4eXp8eLGBrTEqNH": ("Stub #5", f"ar://{ARWEAVE_ID}"),
+}
+
+OFFCHAIN = {
+    "/valid.json": {
+        "name": "Stub #1",
+        "image": f"ipfs://{CID}/1.png",
+        "attributes": [{"trait_type": "Background", "value": "Blue"}],
+        "properties": {
+            "category": "image",
+            "files": [{"uri": f"ipfs://{CID}/1.png", "type": "image/png"}],
+            "creators": [{"address": "4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi", "share": 100}],
+        },
+    },
+    # name mismatch, no image, invalid file and creator entries
+    "/invalid.json": {
+        "name": "Stub #200",
+        "attributes": [],
+        "properties": {
+            "files": [{"uri": 42}, {"uri": f"{HOST}/2.png", "type": 1}],
+            "creators": [{"share": 50}, {"address": "8qbHbw2BbbTHBW1sbeqakYXVKRQM8Ne7pLK7m6CVfeR", "share": "50"}],
+        },
+    },
+    f"/ipfs/{CID}/3.json": {
+        "name": "Stub #3",
+        "image": f"ipfs://{CID}/3.png",
+        "attributes": [],
+        "properties": {"files": [], "creators": []},
+    },
+    f"/{ARWEAVE_ID}": {
+        "name": "Stub #5",
+        "image": f"ar://{ARWEAVE_ID}/5.png",
+        "attributes": [],
+        "properties": {"files": [], "creators": []},
+    },
+}
+
+
+def borsh_string(value):
+    data = value.encode()
+    return len(data).to_bytes(4, "little") + data
+
+
+def core_asset(name, uri):
+    # Key::AssetV1, owner, UpdateAuthority::None, name, uri, seq: None
+    data = bytes([1]) + bytes(32) + bytes([0]) + borsh_string(name) + borsh_string(uri) + bytes([0])
+    return {
+        "data": [base64.b64encode(data).decode(), "base64"],
+        "executable": False,
+        "lamports": 1000000,
+        "owner": MPL_CORE_ID,
+        "rentEpoch": 0,
+        "space": len(data),
+    }
+
+
+class Handler(http.server.BaseHTTPRequestHandler):
+    def log_message(self, *args):
+        pass
+
+    def reply(self, status, body):
+        data = json.dumps(body).encode()
+        self.send_response(status)
+        self.send_header("Content-Type", "application/json")
+        self.send_header("Content-Length", str(len(data)))
+        self.end_headers()
+        self.wfile.write(data)
+
+    def do_GET(self):
+        if self.path in OFFCHAIN:
+            self.reply(200, OFFCHAIN[self.path])
+        else:
+            self.reply(404, {"error": "not found"})
+
+    def do_POST(self):
+        request = json.loads(self.rfile.read(int(self.headers["Content-Length"])))
+        response = {"jsonrpc": "2.0", "id": request["id"]}
+        if request["method"] == "getAccountInfo":
+            asset = ASSETS.get(request["params"][0])
+            response["result"] = {
+                "context": {"slot": 1},
+                "value": asset and core_asset(*asset),
+            }
+        elif request["method"] == "getAsset":
+            # DAS API is supported but knows no assets
+            response["error"] = {"code": -32000, "message": "Asset not found"}
+        else:
+            response["error"] = {"code": -32601, "message": "Method not found"}
+        self.reply(200, response)
+
+
+http.server.ThreadingHTTPServer(("127.0.0.1", PORT), Handler).serve_forever()
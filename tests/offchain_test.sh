@@ -0,0 +1,26 @@
+set -e
+
+SE=./target/debug/se
+
+# accounts and their off-chain metadata are served by a local stub, see tests/offchain_stub.py
+STUB_PORT=8890
+python3 tests/offchain_stub.py $STUB_PORT &
+STUB_PID=$!
+trap "kill $STUB_PID" EXIT
+sleep 1
+export SE_RPC_URL=http://127.0.0.1:$STUB_PORT
+
+echo "\n\nMPL Core NFT with valid off-chain metadata"
+$SE account 4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi --offchain
+
+echo "\n\nMPL Core NFT with invalid off-chain files and creators"
+$SE account 8qbHbw2BbbTHBW1sbeqakYXVKRQM8Ne7pLK7m6CVfeR --offchain -f json
+
+echo "\n\nMPL Core NFT with IPFS off-chain metadata through custom gateway"
+$SE account CktRuQ2mttgRGkXJtyksdKHjUdc2C4TgDzyB98oEzy8 --offchain --gateway http://127.0.0.1:$STUB_PORT
+
+echo "\n\nMPL Core NFT with Arweave off-chain metadata through custom gateway as JSON"
+$SE account 3kC1bzSUJbwhJFcxHBMpyJGr3UcRN4eXp8eLGBrTEqNH --offchain --arweave-gateway http://127.0.0.1:$STUB_PORT -f json
+
+echo "\n\nMPL Core NFT with off-chain metadata not found"
+$SE account GgBaCs3NCBuZN12kCJgAW63ydqohFkHEdfdEXBPzLHq --offchain
@@ -10,6 +10,14 @@ echo "\n\ntransaction WSOL -> GOAT on Raydium swap with short tx alias"
 $SE tx 44cfUnsmWHfS1Kz4PcF9Rj4XoLSivfTYPunTQRLnvqAfhBqrYUV2mWoJUXr5goKAUqVp4g4XWBgLJ9rSYva7cxqq
 sleep 0.5
 
+echo "\n\ntransaction as JSON"
+$SE tx 44cfUnsmWHfS1Kz4PcF9Rj4XoLSivfTYPunTQRLnvqAfhBqrYUV2mWoJUXr5goKAUqVp4g4XWBgLJ9rSYva7cxqq -f json
+sleep 0.5
+
+echo "\n\ndecode signed transaction offline"
+$SE tx decode AQ5bOIZ9p28IvWVRHHvwalO6KThBrsTUc7ezs7WKuv1n46TwX3zYx+zv8EOdjvVkHvBU+w7tFvb6HpVewgkyAwgBAAEDA6EHv/POEL4dcN0Y50vAmWfk1jCbpQ1fHdyGZBJVMbgfK4aTJ+y3XaHd1FzUErScdDaDyDXqniJDQXPikOHcIAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAOVv3J/mqxegJEVkQc/z5yCb0KIBBMcoIm+ujhpQhdJoBAgIAAQwCAAAA6AMAAAAAAAA=
+sleep 0.5
+
 echo "\n\nlist of account transactions"
 $SE account-transactions HEcFC9JDPR2CrMmUZ6nJpDKfbuFsg26DGYEJWw9B988E
 sleep 0.5
@@ -17,3 +25,96 @@ sleep 0.5
 echo "\n\nlist of account transactions with short ac-txs alias"
 $SE ac-txs HEcFC9JDPR2CrMmUZ6nJpDKfbuFsg26DGYEJWw9B988E
 sleep 0.5
+
+echo "\n\nlist of compressed NFT transactions"
+$SE ac-txs 9HNN54hfD3GVy4WkUtXjJdxaTo9tjFzYmEXYN9eHnLZp
+sleep 0.5
+
+echo "\n\nlist of account transactions as JSON"
+$SE ac-txs HEcFC9JDPR2CrMmUZ6nJpDKfbuFsg26DGYEJWw9B988E -f json
+sleep 0.5
+
+echo "\n\nlist of account transactions page by page"
+$SE ac-txs HEcFC9JDPR2CrMmUZ6nJpDKfbuFsg26DGYEJWw9B988E --limit 2 --before 44cfUnsmWHfS1Kz4PcF9Rj4XoLSivfTYPunTQRLnvqAfhBqrYUV2mWoJUXr5goKAUqVp4g4XWBgLJ9rSYva7cxqq
+sleep 0.5
+
+echo "\n\nlist of account transactions in full"
+$SE ac-txs HEcFC9JDPR2CrMmUZ6nJpDKfbuFsg26DGYEJWw9B988E --limit 3 --details
+sleep 0.5
+
+echo "\n\nlist of failed account transactions in a time range"
+$SE ac-txs HEcFC9JDPR2CrMmUZ6nJpDKfbuFsg26DGYEJWw9B988E --failed --since 2024-01-01 --until 2024-07-01T00:00:00Z
+sleep 0.5
+
+echo "\n\nlist of account transactions that invoke Raydium AMM"
+$SE ac-txs HEcFC9JDPR2CrMmUZ6nJpDKfbuFsg26DGYEJWw9B988E --program 675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8 --limit 3
+sleep 0.5
+
+echo "\n\nlist of account transactions in full fetched concurrently"
+$SE ac-txs HEcFC9JDPR2CrMmUZ6nJpDKfbuFsg26DGYEJWw9B988E --limit 20 --details --concurrency 4 --rate-limit 10 -f json
+sleep 0.5
+
+echo "\n\nlist of account transactions as NDJSON"
+$SE ac-txs HEcFC9JDPR2CrMmUZ6nJpDKfbuFsg26DGYEJWw9B988E --limit 5 -f ndjson
+sleep 0.5
+
+echo "\n\nlist of account transactions in full as NDJSON"
+$SE ac-txs HEcFC9JDPR2CrMmUZ6nJpDKfbuFsg26DGYEJWw9B988E --limit 3 --details -f ndjson
+sleep 0.5
+
+echo "\n\nSOL balance history of an account"
+$SE balance-history HEcFC9JDPR2CrMmUZ6nJpDKfbuFsg26DGYEJWw9B988E --limit 5
+sleep 0.5
+
+echo "\n\nUSDC balance history of an account as CSV"
+$SE balance-history HEcFC9JDPR2CrMmUZ6nJpDKfbuFsg26DGYEJWw9B988E --mint EPjFWdd5AvaAZ8FpLLNcp7D6WkVcJGQ2WWtLGrM5Kn8e --limit 5 --csv
+sleep 0.5
+
+echo "\n\ntransfers of a wallet"
+$SE transfers HEcFC9JDPR2CrMmUZ6nJpDKfbuFsg26DGYEJWw9B988E --limit 5
+sleep 0.5
+
+echo "\n\ntransfers of a wallet as CSV"
+$SE transfers HEcFC9JDPR2CrMmUZ6nJpDKfbuFsg26DGYEJWw9B988E --limit 5 --csv
+sleep 0.5
+
+# transfer of 2.5 SOL, signatures are not verified by simulation
+TRANSFER_TX=AQAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABAAED6NRAUIc9uoZap8Fwq0zOZNkIOaNNz9bPcdFOAgVEOxsdXmoe3d8stZt7vAIY4DwwXebBFIWiqg07r8dGa0uOPAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABAgIAAQwCAAAAAPkClQAAAAA=
+
+echo "\n\nAnchor events of a simulated transaction decoded with IDLs, see tests/anchor_events_stub.py"
+python3 tests/anchor_events_stub.py 8891 &
+EVENTS_STUB_PID=$!
+trap "kill $EVENTS_STUB_PID" EXIT
+sleep 1
+SE_RPC_URL=http://127.0.0.1:8891 $SE tx simulate $TRANSFER_TX
+
+# creation of a Core asset with the owner omitted and a cNFT mint into a collection
+NFT_TX=AQAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABAAYJ6NRAUIc9uoZap8Fwq0zOZNkIOaNNz9bPcdFOAgVEOxvxN2SEB8am5iuXsVlLNC3qTs7J2PDMbosJBbvosfW3f4Gmce9uy3BcXHdzcSBjZpojyeHq4tvO2noGc6gFkcncHV5qHt3fLLWbe7wCGOA8MF3mwRSFoqoNO6/HRmtLjjwTgivnw8DJ1Swbupu/cJrNrgMqU9OzzJRxual1Dw1DHgAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAr1SrEL2XpUKgnvezmIndDNOUpMzp36bNyX6+LSNbp0iYi4DreTUoabIkdF9Z3b+KJljKE9xogSEmNRyuB8GlpQu8D8C7R8ovdMQRLpSrE8+jxjTl3BfqywPNGiPNfnh8AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAACBggBAgMABgYFBh0AAAkAAABGRUwgIzI0NzkJAAAAaHR0cHM6Ly94AAcKAAAABAAAAAACCB2ZErIvxZ5WDwcAAABEcmlwICM3AQAAAEQBAAAAdQ==
+
+echo "\n\nNFT operations of a simulated transaction, see tests/nft_operations_stub.py"
+python3 tests/nft_operations_stub.py 8892 &
+NFT_STUB_PID=$!
+trap "kill $EVENTS_STUB_PID $NFT_STUB_PID" EXIT
+sleep 1
+SE_RPC_URL=http://127.0.0.1:8892 $SE tx simulate $NFT_TX
+
+# transfer of 1.5 tokens between token accounts of tests/validator_accounts, the fee payer
+# owns the source account and receives the genesis SOL of the local validator
+TOKEN_TX=AQAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABAAIF6NRAUIc9uoZap8Fwq0zOZNkIOaNNz9bPcdFOAgVEOxuFz2zC5Fi7G8e/RvAUecV3XEZM2wV071Rhp4aW1FzAt5O3Gkxu34K/2XmmslODb8oCUZk8ZfCFTTfi7UVV+pLfD8fbhYdpmxg1dmMJAX23R6Gk9D3RMQ9JTcSvj89sPJYG3fbh12Whk9nL4UbO63msHLSF7V9bN5E6jPWFfv8AqQAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAQQEAQMCAAoMYOMWAAAAAAAG
+
+if command -v solana-test-validator > /dev/null; then
+    echo "\n\nsimulated token transfer with token balances on a local validator"
+    VALIDATOR_LEDGER=$(mktemp -d)
+    solana-test-validator --ledger $VALIDATOR_LEDGER --rpc-port 8893 --faucet-port 8895 --quiet \
+        --mint GfsJWjmGXMfct8JMR9Lm9ySUnniZbnGUTQDbT8ipWf9U \
+        --account-dir tests/validator_accounts &
+    VALIDATOR_PID=$!
+    trap "kill $EVENTS_STUB_PID $NFT_STUB_PID $VALIDATOR_PID; rm -rf $VALIDATOR_LEDGER" EXIT
+    until curl -s http://127.0.0.1:8893 -H "Content-Type: application/json" \
+        -d '{"jsonrpc":"2.0","id":1,"method":"getHealth"}' | grep -q '"ok"'; do
+        sleep 1
+    done
+    SE_RPC_URL=http://127.0.0.1:8893 $SE tx simulate $TOKEN_TX
+else
+    echo "\n\nsolana-test-validator is not installed, skipping simulation on a local validator"
+fi